@@ -6,7 +6,7 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Data, Fields, Attribute, Meta, Lit, Expr};
+use syn::{parse_macro_input, DeriveInput, Data, Fields, Attribute, Expr};
 
 /// Derive macro for generating repository implementations
 ///
@@ -21,6 +21,17 @@ use syn::{parse_macro_input, DeriveInput, Data, Fields, Attribute, Meta, Lit, Ex
 /// - `#[repository(soft_delete)]` - Enable soft delete functionality
 /// - `#[repository(searchable_fields(field1, field2))]` - Fields for text search
 /// - `#[repository(filterable_fields(field1, field2))]` - Fields for filtering
+/// - `#[repository(sortable_fields(field1, field2))]` - Fields `sort_by` may name (optional, defaults to every field)
+/// - `#[repository(aggregatable_fields(field1, field2))]` - Fields aggregates may name (optional, defaults to every
+///   numeric and date/time field)
+/// - `#[repository(full_text(config = "english", fields(field1, field2)))]` - PostgreSQL full-text search over
+///   the given fields instead of `ILIKE` on `searchable_fields` (`config` defaults to `simple`)
+/// - `#[repository(strict = false)]` - Ignore filters on fields that aren't `filterable_fields` and the `deleted`
+///   scope without soft delete, instead of returning a validation error (strict by default)
+/// - `#[repository(max_per_page = 100, default_per_page = 25)]` - Largest page size searches may request
+///   (defaults to 1000, at most 10000) and the page size used when `per_page` is 0 (defaults to 10)
+/// - `#[repository(backend = "sqlite")]` - Database backend (`postgres`, `mysql`, `sqlite` or the path of a
+///   custom `DatabaseBackend` type, defaults to `sqlx_repository::backends::DefaultBackend`)
 ///
 /// ## Table Name Resolution
 ///
@@ -40,6 +51,7 @@ use syn::{parse_macro_input, DeriveInput, Data, Fields, Attribute, Meta, Lit, Ex
 /// #[repository(soft_delete)]
 /// #[repository(searchable_fields(name, email))]
 /// #[repository(filterable_fields(status, department))]
+/// #[repository(sortable_fields(name, created_at))]
 /// pub struct User {
 ///     pub id: i32,
 ///     pub name: String,
@@ -90,11 +102,19 @@ fn derive_repository_impl(input: DeriveInput) -> Result<TokenStream, syn::Error>
     let primary_key_type = validate_and_extract_primary_key_type(&input)?;
     
     // Extract attributes with fallback to pluralized struct name
-    let table_name = extract_table_name(&input.attrs)
+    let table_name = extract_table_name(&input.attrs)?
         .unwrap_or_else(|| pluralize(&name.to_string().to_lowercase()));
-    let soft_delete = has_repository_attribute(&input.attrs, "soft_delete");
-    let searchable_fields = extract_field_list(&input.attrs, "searchable_fields");
-    let filterable_fields = extract_field_list(&input.attrs, "filterable_fields");
+    let soft_delete = has_repository_attribute(&input.attrs, "soft_delete")?;
+    let searchable_fields = extract_field_list(&input.attrs, "searchable_fields")?;
+    let filterable_fields = extract_field_list(&input.attrs, "filterable_fields")?;
+    let sortable_fields = extract_field_list(&input.attrs, "sortable_fields")?;
+    let aggregatable_fields = extract_field_list(&input.attrs, "aggregatable_fields")?;
+    let full_text = extract_full_text(&input)?;
+    let strict = extract_bool_value(&input.attrs, "strict")?.unwrap_or(true);
+    let max_per_page = extract_u32_value(&input.attrs, "max_per_page")?;
+    let default_per_page = extract_u32_value(&input.attrs, "default_per_page")?;
+    let backend = resolve_backend(&input)?;
+    let uses_uuid_primary_key = is_uuid_type(&primary_key_type);
     
     // Validate soft delete requirements
     if soft_delete {
@@ -103,12 +123,59 @@ fn derive_repository_impl(input: DeriveInput) -> Result<TokenStream, syn::Error>
     
     // Validate supported field types
     validate_field_types(&input)?;
+    validate_attribute_fields(&input, "Sortable", &sortable_fields)?;
+    validate_attribute_fields(&input, "Aggregatable", &aggregatable_fields)?;
+    validate_page_sizes(&input, max_per_page, default_per_page)?;
+    if let Some((_, fields)) = &full_text {
+        validate_attribute_fields(&input, "Full-text", fields)?;
+    }
 
     // Extract field information
     let field_names = extract_field_names(&input);
+    let default_per_page = default_per_page.map(|size| quote! {
+        fn default_per_page() -> u32 {
+            #size
+        }
+    });
+    let max_per_page = max_per_page.map(|size| quote! {
+        fn max_per_page() -> u32 {
+            #size
+        }
+    });
+    let full_text = match &full_text {
+        Some((config, fields)) => quote! {
+            Some(sqlx_repository::FullTextConfig { config: #config, fields: &[#(#fields),*] })
+        },
+        None => quote! { None },
+    };
+    let field_types = extract_field_types(&input);
     let create_fields = generate_create_struct_fields(&field_names, &input);
     let update_fields = generate_update_struct_fields(&field_names, &input);
 
+    // Bind a freshly generated UUID first if the primary key needs one
+    let bind_generated_id = if uses_uuid_primary_key {
+        quote! {
+            let generated_id = <#primary_key_type as sqlx_repository::IdGenerator<#primary_key_type>>::generate();
+            query_builder = query_builder.bind(generated_id);
+        }
+    } else {
+        quote! {}
+    };
+
+    // Id of the inserted row for backends that cannot return it directly
+    let inserted_id = if uses_uuid_primary_key {
+        quote! { generated_id }
+    } else {
+        quote! {
+            <#backend as sqlx_repository::backends::DatabaseBackend>::last_insert_id(&result)
+                .and_then(|id| <#primary_key_type>::try_from(id).ok())
+                .ok_or_else(|| sqlx_repository::RepositoryError::unsupported_feature(
+                    "last_insert_id",
+                    <#backend as sqlx_repository::backends::DatabaseBackend>::NAME,
+                ))?
+        }
+    };
+
     let expanded = quote! {
         /// Auto-generated Create type for new entity creation
         #[derive(Debug, Clone, serde::Deserialize)]
@@ -124,18 +191,28 @@ fn derive_repository_impl(input: DeriveInput) -> Result<TokenStream, syn::Error>
 
         /// Auto-generated Repository implementation
         pub struct #repository_name {
-            pool: sqlx::PgPool,
+            pool: sqlx::Pool<<#backend as sqlx_repository::backends::DatabaseBackend>::Database>,
         }
 
         impl #repository_name {
             /// Create a new repository instance with the given database pool
-            pub fn new(pool: sqlx::PgPool) -> Self {
+            pub fn new(pool: sqlx::Pool<<#backend as sqlx_repository::backends::DatabaseBackend>::Database>) -> Self {
                 Self { pool }
             }
         }
 
+        impl sqlx_repository::TransactionalRepository for #repository_name {
+            type Entity = #name;
+            type Id = #primary_key_type;
+            type Backend = #backend;
+
+            fn from_pool(pool: sqlx::Pool<<#backend as sqlx_repository::backends::DatabaseBackend>::Database>) -> Self {
+                Self::new(pool)
+            }
+        }
+
         #[async_trait::async_trait]
-        impl sqlx_repository::Repository<#name, #primary_key_type> for #repository_name {
+        impl sqlx_repository::Repository<#name, #primary_key_type, #backend> for #repository_name {
             type CreateType = #create_type;
             type UpdateType = #update_type;
 
@@ -155,99 +232,102 @@ fn derive_repository_impl(input: DeriveInput) -> Result<TokenStream, syn::Error>
                 &[#(#filterable_fields),*]
             }
 
-            fn pool(&self) -> &sqlx::PgPool {
+            fn sortable_fields() -> &'static [&'static str] {
+                &[#(#sortable_fields),*]
+            }
+
+            fn aggregatable_fields() -> &'static [&'static str] {
+                &[#(#aggregatable_fields),*]
+            }
+
+            fn strict_params() -> bool {
+                #strict
+            }
+
+            #default_per_page
+
+            #max_per_page
+
+            fn full_text() -> Option<sqlx_repository::FullTextConfig> {
+                #full_text
+            }
+
+            fn field_types() -> &'static [(&'static str, sqlx_repository::FieldType)] {
+                &[#(#field_types),*]
+            }
+
+            fn pool(&self) -> &sqlx::Pool<<#backend as sqlx_repository::backends::DatabaseBackend>::Database> {
                 &self.pool
             }
 
-            async fn create(&self, data: Self::CreateType) -> sqlx_repository::RepositoryResult<#name> {
+            async fn create_in(
+                &self,
+                conn: &mut <<#backend as sqlx_repository::backends::DatabaseBackend>::Database as sqlx::Database>::Connection,
+                data: Self::CreateType,
+            ) -> sqlx_repository::RepositoryResult<#name> {
                 let field_names = vec![#(stringify!(#field_names)),*];
                 
-                // For UUID primary keys, we need to generate the ID and include it in the insert
-                let (all_field_names, all_placeholders, needs_uuid_generation) = if stringify!(#primary_key_type) == "Uuid" {
+                // For UUID primary keys, we need to generate the ID and include it in the insert;
+                // integer primary keys use auto-increment (exclude id from insert)
+                let all_field_names = if #uses_uuid_primary_key {
                     let mut names = vec!["id"];
                     names.extend(field_names.iter().cloned());
-                    let placeholders: Vec<String> = (1..=names.len()).map(|i| format!("${}", i)).collect();
-                    (names, placeholders, true)
+                    names
                 } else {
-                    // For integer primary keys, use auto-increment (exclude id from insert)
-                    let placeholders: Vec<String> = (1..=field_names.len()).map(|i| format!("${}", i)).collect();
-                    (field_names.clone(), placeholders, false)
-                };
-                
-                let query = if Self::soft_delete_enabled() {
-                    format!(
-                        "INSERT INTO {} ({}, created_at, updated_at) VALUES ({}, NOW(), NOW()) RETURNING *",
-                        Self::table_name(),
-                        all_field_names.join(", "),
-                        all_placeholders.join(", ")
-                    )
-                } else {
-                    format!(
-                        "INSERT INTO {} ({}) VALUES ({}) RETURNING *",
-                        Self::table_name(),
-                        all_field_names.join(", "),
-                        all_placeholders.join(", ")
-                    )
+                    field_names
                 };
+                let query = <#backend as sqlx_repository::backends::DatabaseBackend>::insert_sql(
+                    Self::table_name(),
+                    &all_field_names,
+                    Self::soft_delete_enabled(),
+                );
 
-                let mut query_builder = sqlx::query_as(&query);
+                let mut query_builder = sqlx::query(&query);
                 
-                // Bind UUID first if needed
-                if needs_uuid_generation {
-                    query_builder = query_builder.bind(<#primary_key_type as sqlx_repository::IdGenerator<#primary_key_type>>::generate());
-                }
+                #bind_generated_id
                 
                 // Bind all other fields
                 #(
                     query_builder = query_builder.bind(&data.#field_names);
                 )*
 
-                query_builder
-                    .fetch_one(self.pool())
-                    .await
-                    .map_err(sqlx_repository::RepositoryError::from)
+                if <#backend as sqlx_repository::backends::DatabaseBackend>::SUPPORTS_RETURNING {
+                    let row = query_builder.fetch_one(&mut *conn).await?;
+                    return sqlx::FromRow::from_row(&row).map_err(sqlx_repository::RepositoryError::from);
+                }
+
+                // No RETURNING support: read the inserted row back by id
+                let result = query_builder.execute(&mut *conn).await?;
+                let id: #primary_key_type = #inserted_id;
+                self.find_by_id_in(conn, id)
+                    .await?
+                    .ok_or_else(|| sqlx_repository::RepositoryError::not_found(Self::table_name(), "id", id))
             }
 
-            async fn update(&self, id: #primary_key_type, data: Self::UpdateType) -> sqlx_repository::RepositoryResult<Option<#name>> {
-                let mut set_parts = Vec::new();
-                let mut has_updates = false;
-                
+            async fn update_in(
+                &self,
+                conn: &mut <<#backend as sqlx_repository::backends::DatabaseBackend>::Database as sqlx::Database>::Connection,
+                id: #primary_key_type,
+                data: Self::UpdateType,
+            ) -> sqlx_repository::RepositoryResult<Option<#name>> {
+                let mut set_columns: Vec<&str> = Vec::new();
                 #(
                     if data.#field_names.is_some() {
-                        has_updates = true;
+                        set_columns.push(stringify!(#field_names));
                     }
                 )*
 
-                if !has_updates {
-                    return self.find_by_id(id).await;
+                if set_columns.is_empty() {
+                    return self.find_by_id_in(conn, id).await;
                 }
 
-                let mut param_count = 0;
-                #(
-                    if data.#field_names.is_some() {
-                        param_count += 1;
-                        set_parts.push(format!("{} = ${}", stringify!(#field_names), param_count));
-                    }
-                )*
+                let query_str = <#backend as sqlx_repository::backends::DatabaseBackend>::update_sql(
+                    Self::table_name(),
+                    &set_columns,
+                    Self::soft_delete_enabled(),
+                );
 
-                param_count += 1;
-                let query_str = if Self::soft_delete_enabled() {
-                    format!(
-                        "UPDATE {} SET {}, updated_at = NOW() WHERE id = ${} AND deleted_at IS NULL RETURNING *",
-                        Self::table_name(),
-                        set_parts.join(", "),
-                        param_count
-                    )
-                } else {
-                    format!(
-                        "UPDATE {} SET {} WHERE id = ${} RETURNING *",
-                        Self::table_name(),
-                        set_parts.join(", "),
-                        param_count
-                    )
-                };
-
-                let mut query_builder = sqlx::query_as(&query_str);
+                let mut query_builder = sqlx::query(&query_str);
                 
                 #(
                     if let Some(ref value) = data.#field_names {
@@ -256,11 +336,20 @@ fn derive_repository_impl(input: DeriveInput) -> Result<TokenStream, syn::Error>
                 )*
                 
                 query_builder = query_builder.bind(id);
-                
-                query_builder
-                    .fetch_optional(self.pool())
-                    .await
-                    .map_err(sqlx_repository::RepositoryError::from)
+
+                if <#backend as sqlx_repository::backends::DatabaseBackend>::SUPPORTS_RETURNING {
+                    return match query_builder.fetch_optional(&mut *conn).await? {
+                        Some(row) => sqlx::FromRow::from_row(&row).map(Some).map_err(sqlx_repository::RepositoryError::from),
+                        None => Ok(None),
+                    };
+                }
+
+                // No RETURNING support: read the updated row back by id
+                let result = query_builder.execute(&mut *conn).await?;
+                if <#backend as sqlx_repository::backends::DatabaseBackend>::rows_affected(&result) == 0 {
+                    return Ok(None);
+                }
+                self.find_by_id_in(conn, id).await
             }
         }
     };
@@ -300,76 +389,258 @@ fn validate_soft_delete_fields(input: &DeriveInput) -> Result<(), syn::Error> {
     Ok(())
 }
 
+/// Validate that every field an attribute lists (e.g. `sortable_fields`) is a field of the struct
+fn validate_attribute_fields(input: &DeriveInput, label: &str, listed_fields: &[String]) -> Result<(), syn::Error> {
+    let fields = match &input.data {
+        Data::Struct(data_struct) => {
+            match &data_struct.fields {
+                Fields::Named(fields_named) => &fields_named.named,
+                _ => return Err(syn::Error::new_spanned(input, "Repository derive only supports structs with named fields")),
+            }
+        }
+        _ => return Err(syn::Error::new_spanned(input, "Repository derive only supports structs")),
+    };
+
+    for listed in listed_fields {
+        if !fields.iter().any(|field| field.ident.as_ref().is_some_and(|ident| ident == listed)) {
+            return Err(syn::Error::new_spanned(
+                input,
+                format!("{} field '{}' is not a field of {}", label, listed, input.ident),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Largest `max_per_page` (and `default_per_page`) an entity may declare, so
+/// every page of rows is a sane amount to fetch and hold in memory
+const PAGE_SIZE_LIMIT: u32 = 10_000;
+
+/// Check that page sizes are positive, within `PAGE_SIZE_LIMIT`, and the default doesn't exceed the maximum
+fn validate_page_sizes(input: &DeriveInput, max_per_page: Option<u32>, default_per_page: Option<u32>) -> Result<(), syn::Error> {
+    if max_per_page == Some(0) || default_per_page == Some(0) {
+        return Err(syn::Error::new_spanned(input, "max_per_page and default_per_page must be at least 1"));
+    }
+    if let Some(size) = max_per_page.into_iter().chain(default_per_page).find(|size| *size > PAGE_SIZE_LIMIT) {
+        return Err(syn::Error::new_spanned(
+            input,
+            format!("Page size {} is too large; max_per_page and default_per_page can be at most {}", size, PAGE_SIZE_LIMIT),
+        ));
+    }
+    if let (Some(max), Some(default)) = (max_per_page, default_per_page) {
+        if default > max {
+            return Err(syn::Error::new_spanned(
+                input,
+                format!("default_per_page ({}) must not exceed max_per_page ({})", default, max),
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Extract table name from repository attributes
-fn extract_table_name(attrs: &[Attribute]) -> Option<String> {
+fn extract_table_name(attrs: &[Attribute]) -> Result<Option<String>, syn::Error> {
+    extract_string_value(attrs, "table")
+}
+
+/// Resolve the backend type path from `#[repository(backend = "...")]`
+fn resolve_backend(input: &DeriveInput) -> Result<proc_macro2::TokenStream, syn::Error> {
+    match extract_string_value(&input.attrs, "backend")?.as_deref() {
+        None => Ok(quote! { sqlx_repository::backends::DefaultBackend }),
+        Some("postgres") => Ok(quote! { sqlx_repository::backends::postgres::PostgresBackend }),
+        Some("mysql") => Ok(quote! { sqlx_repository::backends::mysql::MySqlBackend }),
+        Some("sqlite") => Ok(quote! { sqlx_repository::backends::sqlite::SqliteBackend }),
+        // Any other path names a custom `DatabaseBackend` implementation
+        Some(path) if path.contains("::") => syn::parse_str::<syn::Path>(path)
+            .map(|path| quote! { #path })
+            .map_err(|e| syn::Error::new_spanned(input, format!("Invalid backend path '{}': {}", path, e))),
+        Some(other) => Err(syn::Error::new_spanned(
+            input,
+            format!("Unknown repository backend '{}'.\n\nSupported backends: \"postgres\", \"mysql\", \"sqlite\", or the path of a custom DatabaseBackend type\n\nExample:\n#[derive(Repository)]\n#[repository(backend = \"sqlite\")]\npub struct User {{\n    pub id: i32,\n    pub name: String,\n}}", other)
+        )),
+    }
+}
+
+/// Check whether a type is `Uuid` (with or without a path prefix)
+fn is_uuid_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Uuid"),
+        _ => false,
+    }
+}
+
+/// Extract a string value (like `table = "users"`) from repository attributes
+fn extract_string_value(attrs: &[Attribute], name: &str) -> Result<Option<String>, syn::Error> {
     for attr in attrs {
-        if attr.path().is_ident("repository") {
-            if let Meta::List(meta_list) = &attr.meta {
-                if let Ok(parsed) = meta_list.parse_args::<Meta>() {
-                    if let Meta::NameValue(nv) = parsed {
-                        if nv.path.is_ident("table") {
-                            if let Expr::Lit(lit_expr) = nv.value {
-                                if let Lit::Str(lit_str) = lit_expr.lit {
-                                    return Some(lit_str.value());
-                                }
-                            }
-                        }
-                    }
-                }
+        if !attr.path().is_ident("repository") {
+            continue;
+        }
+        let mut value = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) {
+                value = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            } else {
+                skip_meta_value(&meta)?;
             }
+            Ok(())
+        })?;
+        if value.is_some() {
+            return Ok(value);
         }
     }
-    None
+    Ok(None)
+}
+
+/// Extract a boolean value (like `strict = false`) from repository attributes
+fn extract_bool_value(attrs: &[Attribute], name: &str) -> Result<Option<bool>, syn::Error> {
+    for attr in attrs {
+        if !attr.path().is_ident("repository") {
+            continue;
+        }
+        let mut value = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) {
+                value = Some(meta.value()?.parse::<syn::LitBool>()?.value);
+            } else {
+                skip_meta_value(&meta)?;
+            }
+            Ok(())
+        })?;
+        if value.is_some() {
+            return Ok(value);
+        }
+    }
+    Ok(None)
+}
+
+/// Extract an integer value (like `max_per_page = 100`) from repository attributes
+fn extract_u32_value(attrs: &[Attribute], name: &str) -> Result<Option<u32>, syn::Error> {
+    for attr in attrs {
+        if !attr.path().is_ident("repository") {
+            continue;
+        }
+        let mut value = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) {
+                value = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse::<u32>()?);
+            } else {
+                skip_meta_value(&meta)?;
+            }
+            Ok(())
+        })?;
+        if value.is_some() {
+            return Ok(value);
+        }
+    }
+    Ok(None)
 }
 
 /// Check if a repository attribute exists (like soft_delete)
-fn has_repository_attribute(attrs: &[Attribute], name: &str) -> bool {
+fn has_repository_attribute(attrs: &[Attribute], name: &str) -> Result<bool, syn::Error> {
     for attr in attrs {
-        if attr.path().is_ident("repository") {
-            if let Meta::List(meta_list) = &attr.meta {
-                let mut found = false;
-                let _ = meta_list.parse_nested_meta(|meta| {
-                    if meta.path.is_ident(name) {
-                        found = true;
-                    }
-                    Ok(())
-                });
-                if found {
-                    return true;
-                }
+        if !attr.path().is_ident("repository") {
+            continue;
+        }
+        let mut found = false;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) {
+                found = true;
+            } else {
+                skip_meta_value(&meta)?;
             }
+            Ok(())
+        })?;
+        if found {
+            return Ok(true);
         }
     }
-    false
+    Ok(false)
 }
 
 /// Extract field list from repository attributes (like searchable_fields)
-fn extract_field_list(attrs: &[Attribute], attr_name: &str) -> Vec<String> {
+fn extract_field_list(attrs: &[Attribute], attr_name: &str) -> Result<Vec<String>, syn::Error> {
     for attr in attrs {
-        if attr.path().is_ident("repository") {
-            if let Meta::List(meta_list) = &attr.meta {
-                let mut fields = Vec::new();
-                let _ = meta_list.parse_nested_meta(|meta| {
-                    if meta.path.is_ident(attr_name) {
-                        if meta.input.peek(syn::token::Paren) {
-                            let content;
-                            syn::parenthesized!(content in meta.input);
-                            let field_list: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]> = 
-                                content.parse_terminated(|input| input.parse(), syn::Token![,])?;
-                            for field in field_list {
-                                fields.push(field.to_string());
-                            }
-                        }
-                    }
+        if !attr.path().is_ident("repository") {
+            continue;
+        }
+        let mut fields = Vec::new();
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(attr_name) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let field_list: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]> =
+                    content.parse_terminated(|input| input.parse(), syn::Token![,])?;
+                fields.extend(field_list.iter().map(ToString::to_string));
+            } else {
+                skip_meta_value(&meta)?;
+            }
+            Ok(())
+        })?;
+        if !fields.is_empty() {
+            return Ok(fields);
+        }
+    }
+    Ok(Vec::new())
+}
+
+/// Skip the `= value` or `(...)` of a repository attribute handled elsewhere
+fn skip_meta_value(meta: &syn::meta::ParseNestedMeta) -> Result<(), syn::Error> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        content.parse::<proc_macro2::TokenStream>()?;
+    }
+    Ok(())
+}
+
+/// Extract `#[repository(full_text(config = "...", fields(...)))]` as the config and field names
+fn extract_full_text(input: &DeriveInput) -> Result<Option<(String, Vec<String>)>, syn::Error> {
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repository") {
+            continue;
+        }
+        let mut full_text = None;
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("full_text") {
+                // Other repository attributes are parsed elsewhere
+                return skip_meta_value(&meta);
+            }
+
+            let mut config = "simple".to_string();
+            let mut fields = Vec::new();
+            meta.parse_nested_meta(|nested| {
+                if nested.path.is_ident("config") {
+                    config = nested.value()?.parse::<syn::LitStr>()?.value();
                     Ok(())
-                });
-                if !fields.is_empty() {
-                    return fields;
+                } else if nested.path.is_ident("fields") {
+                    let content;
+                    syn::parenthesized!(content in nested.input);
+                    let field_list: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]> =
+                        content.parse_terminated(|input| input.parse(), syn::Token![,])?;
+                    fields.extend(field_list.iter().map(|field| field.to_string()));
+                    Ok(())
+                } else {
+                    Err(nested.error("expected `config = \"...\"` or `fields(...)` in full_text"))
                 }
+            })?;
+            if fields.is_empty() {
+                return Err(meta.error("full_text needs at least one field: full_text(fields(name, bio))"));
             }
+            full_text = Some((config, fields));
+            Ok(())
+        })?;
+        if full_text.is_some() {
+            return Ok(full_text);
         }
     }
-    Vec::new()
+    Ok(None)
 }
 
 /// Extract field names from struct, excluding metadata fields
@@ -410,6 +681,61 @@ fn get_field_type<'a>(input: &'a DeriveInput, field_name: &syn::Ident) -> Option
     }
 }
 
+/// Map every struct field to the `FieldType` its filter values are bound as
+fn extract_field_types(input: &DeriveInput) -> Vec<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(fields_named) => &fields_named.named,
+            _ => return vec![],
+        },
+        _ => return vec![],
+    };
+
+    fields
+        .iter()
+        .filter_map(|field| {
+            let name = field.ident.as_ref()?.to_string();
+            let field_type = quote::format_ident!("{}", field_type_variant(&field.ty));
+            Some(quote! { (#name, sqlx_repository::FieldType::#field_type) })
+        })
+        .collect()
+}
+
+/// Name of the `FieldType` variant for a Rust type, looking through `Option<T>`
+fn field_type_variant(ty: &syn::Type) -> &'static str {
+    let segment = match ty {
+        syn::Type::Path(type_path) => match type_path.path.segments.last() {
+            Some(segment) => segment,
+            None => return "Text",
+        },
+        _ => return "Text",
+    };
+
+    if segment.ident == "Option" {
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                return field_type_variant(inner);
+            }
+        }
+        return "Text";
+    }
+
+    match segment.ident.to_string().as_str() {
+        "bool" => "Bool",
+        "i16" => "I16",
+        "i32" | "u16" => "I32",
+        "i64" | "u32" | "u64" => "I64",
+        "f32" => "F32",
+        "f64" => "F64",
+        "Uuid" => "Uuid",
+        "DateTime" => "DateTime",
+        "NaiveDateTime" => "NaiveDateTime",
+        "NaiveDate" => "NaiveDate",
+        "NaiveTime" => "NaiveTime",
+        _ => "Text",
+    }
+}
+
 /// Generate Create struct fields (non-optional types)
 fn generate_create_struct_fields(field_names: &[syn::Ident], input: &DeriveInput) -> Vec<proc_macro2::TokenStream> {
     field_names.iter().map(|name| {
@@ -439,8 +765,8 @@ fn generate_update_struct_fields(field_names: &[syn::Ident], input: &DeriveInput
 /// - Words ending in 's', 'sh', 'ch', 'x', 'z' -> add 'es' (e.g., "box" -> "boxes")
 /// - All other words -> add 's' (e.g., "user" -> "users")
 fn pluralize(word: &str) -> String {
-    if word.ends_with('y') {
-        format!("{}ies", &word[..word.len()-1])
+    if let Some(stem) = word.strip_suffix('y') {
+        format!("{}ies", stem)
     } else if word.ends_with('z') && !word.ends_with("zz") {
         format!("{}zes", word)  // Double single 'z' before adding 'es'
     } else if word.ends_with("s") || word.ends_with("sh") || word.ends_with("ch") || word.ends_with("x") || word.ends_with("z") {
@@ -595,6 +921,18 @@ fn validate_field_types(input: &DeriveInput) -> Result<(), syn::Error> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_field_type_variant() {
+        let variant = |ty: &str| field_type_variant(&syn::parse_str::<syn::Type>(ty).unwrap());
+        assert_eq!(variant("bool"), "Bool");
+        assert_eq!(variant("i32"), "I32");
+        assert_eq!(variant("Option<i64>"), "I64");
+        assert_eq!(variant("chrono::DateTime<chrono::Utc>"), "DateTime");
+        assert_eq!(variant("Option<uuid::Uuid>"), "Uuid");
+        assert_eq!(variant("String"), "Text");
+        assert_eq!(variant("Vec<String>"), "Text");
+    }
+
     #[test]
     fn test_pluralize_basic() {
         assert_eq!(pluralize("user"), "users");
@@ -25,6 +25,7 @@ fn bench_search_params_creation(c: &mut Criterion) {
                 sort_by: Some("name".to_string()),
                 sort_order: SortOrder::Desc,
                 scope: RecordScope::All,
+                ..Default::default()
             })
         })
     });
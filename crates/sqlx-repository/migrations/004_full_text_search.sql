@@ -0,0 +1,6 @@
+-- Full-text search index for posts
+-- The expression must match the one the repository generates for
+-- #[repository(full_text(config = "english", fields(title, content)))]
+
+CREATE INDEX idx_posts_full_text ON posts
+    USING GIN (to_tsvector('english', coalesce("title", '') || ' ' || coalesce("content", '')));
@@ -0,0 +1,6 @@
+-- Trigram similarity for fuzzy search (MatchMode::Fuzzy)
+-- The index serves the `%` operator used when no similarity_threshold is given
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_users_name_trgm ON users USING GIN (name gin_trgm_ops);
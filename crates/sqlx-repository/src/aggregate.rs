@@ -0,0 +1,235 @@
+//! Aggregate queries over repository tables.
+//!
+//! An [`AggregateQuery`] lists `COUNT`, `SUM`, `AVG`, `MIN` and `MAX`
+//! expressions and optional group-by fields. [`Repository::aggregate`] runs
+//! it with the text query, scope and filters of a [`SearchParams`] and
+//! returns one [`AggregateRow`] per group:
+//!
+//! ```rust,ignore
+//! let rows = order_repo.aggregate(
+//!     SearchParams { filters, ..Default::default() },
+//!     AggregateQuery::new([Aggregate::count(), Aggregate::sum("amount")]).group_by(["status"]),
+//! ).await?;
+//! let shipped_total = &rows[0].values["sum_amount"];
+//! ```
+//!
+//! Aggregated fields must be listed in `aggregatable_fields` (or, when that
+//! list is empty, be any numeric or date/time field); group-by fields must be
+//! `filterable_fields`.
+//!
+//! [`Repository::aggregate`]: crate::Repository::aggregate
+//! [`SearchParams`]: crate::SearchParams
+
+use crate::error::{RepositoryError, RepositoryResult};
+use crate::filter::{FieldType, FilterValue};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Aggregate function applied to a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregateFunc {
+    /// Number of rows, or of non-`NULL` values of a field
+    Count,
+    /// Sum of a numeric field
+    Sum,
+    /// Mean of a numeric field
+    Avg,
+    /// Smallest value of a numeric or date/time field
+    Min,
+    /// Largest value of a numeric or date/time field
+    Max,
+}
+
+impl AggregateFunc {
+    /// Type the result is read back as, for a field of `field_type`
+    ///
+    /// Integer sums widen to `i64` and means are always `f64`; `MIN` and
+    /// `MAX` keep the field's type.
+    pub fn result_type(self, field_type: FieldType) -> FieldType {
+        match self {
+            AggregateFunc::Count => FieldType::I64,
+            AggregateFunc::Avg => FieldType::F64,
+            AggregateFunc::Sum => match field_type {
+                FieldType::F32 | FieldType::F64 => FieldType::F64,
+                _ => FieldType::I64,
+            },
+            AggregateFunc::Min | AggregateFunc::Max => field_type,
+        }
+    }
+
+    /// Whether the function applies to a field of `field_type`
+    fn accepts(self, field_type: FieldType) -> bool {
+        match self {
+            AggregateFunc::Count => true,
+            AggregateFunc::Sum | AggregateFunc::Avg => field_type.is_numeric(),
+            AggregateFunc::Min | AggregateFunc::Max => field_type.is_numeric() || field_type.is_temporal(),
+        }
+    }
+}
+
+impl fmt::Display for AggregateFunc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AggregateFunc::Count => "count",
+            AggregateFunc::Sum => "sum",
+            AggregateFunc::Avg => "avg",
+            AggregateFunc::Min => "min",
+            AggregateFunc::Max => "max",
+        })
+    }
+}
+
+/// One aggregate expression, such as `SUM(amount)`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Aggregate {
+    /// Function to apply
+    pub func: AggregateFunc,
+    /// Field the function applies to; `None` counts rows
+    #[serde(default)]
+    pub field: Option<String>,
+    /// Key of the result in [`AggregateRow::values`]; defaults to [`Aggregate::name`]
+    #[serde(default)]
+    pub alias: Option<String>,
+}
+
+impl Aggregate {
+    /// Apply `func` to `field`
+    pub fn new(func: AggregateFunc, field: impl Into<String>) -> Self {
+        Self {
+            func,
+            field: Some(field.into()),
+            alias: None,
+        }
+    }
+
+    /// Count rows (`COUNT(*)`)
+    pub fn count() -> Self {
+        Self {
+            func: AggregateFunc::Count,
+            field: None,
+            alias: None,
+        }
+    }
+
+    /// Count non-`NULL` values of `field`
+    pub fn count_field(field: impl Into<String>) -> Self {
+        Self::new(AggregateFunc::Count, field)
+    }
+
+    /// Sum `field`
+    pub fn sum(field: impl Into<String>) -> Self {
+        Self::new(AggregateFunc::Sum, field)
+    }
+
+    /// Average `field`
+    pub fn avg(field: impl Into<String>) -> Self {
+        Self::new(AggregateFunc::Avg, field)
+    }
+
+    /// Smallest value of `field`
+    pub fn min(field: impl Into<String>) -> Self {
+        Self::new(AggregateFunc::Min, field)
+    }
+
+    /// Largest value of `field`
+    pub fn max(field: impl Into<String>) -> Self {
+        Self::new(AggregateFunc::Max, field)
+    }
+
+    /// Report the result under `alias`
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.alias = Some(alias.into());
+        self
+    }
+
+    /// Key of the result: the alias, or `count` / `{func}_{field}`
+    pub fn name(&self) -> String {
+        match (&self.alias, &self.field) {
+            (Some(alias), _) => alias.clone(),
+            (None, Some(field)) => format!("{}_{}", self.func, field),
+            (None, None) => self.func.to_string(),
+        }
+    }
+
+    /// Check the aggregate against the entity's fields
+    ///
+    /// Only `count` may leave out the field. The field must be listed in
+    /// `aggregatable` (any field when that is empty) and have a type the
+    /// function accepts.
+    pub(crate) fn validate(&self, aggregatable: &[&str], field_types: &[(&str, FieldType)]) -> RepositoryResult<()> {
+        let Some(field) = &self.field else {
+            if self.func == AggregateFunc::Count {
+                return Ok(());
+            }
+            return Err(RepositoryError::validation(format!("{} needs a field to aggregate", self.func)));
+        };
+        let field_type = field_types
+            .iter()
+            .find(|(name, _)| name == field)
+            .map(|(_, field_type)| *field_type)
+            .filter(|_| aggregatable.is_empty() || aggregatable.contains(&field.as_str()));
+        match field_type {
+            Some(field_type) if self.func.accepts(field_type) => Ok(()),
+            _ => Err(RepositoryError::validation(format!(
+                "Field '{}' cannot be aggregated with {}",
+                field, self.func
+            ))),
+        }
+    }
+}
+
+/// Aggregates to compute, optionally per group
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AggregateQuery {
+    /// Expressions to compute
+    pub aggregates: Vec<Aggregate>,
+    /// Fields to group rows by; empty aggregates every match together
+    #[serde(default)]
+    pub group_by: Vec<String>,
+}
+
+impl AggregateQuery {
+    /// Compute `aggregates` over every match
+    pub fn new(aggregates: impl IntoIterator<Item = Aggregate>) -> Self {
+        Self {
+            aggregates: aggregates.into_iter().collect(),
+            group_by: Vec::new(),
+        }
+    }
+
+    /// Compute the aggregates per distinct combination of `fields`
+    pub fn group_by<S: Into<String>>(mut self, fields: impl IntoIterator<Item = S>) -> Self {
+        self.group_by = fields.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Aggregates of one group
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AggregateRow {
+    /// Value of each group-by field, `None` for `NULL`
+    pub group: HashMap<String, Option<FilterValue>>,
+    /// Result of each aggregate by name, `None` when there was nothing to aggregate
+    pub values: HashMap<String, Option<FilterValue>>,
+}
+
+impl AggregateRow {
+    /// Result of the aggregate named `name` as an integer
+    pub fn get_i64(&self, name: &str) -> Option<i64> {
+        match self.values.get(name)? {
+            Some(FilterValue::Int(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Result of the aggregate named `name` as a float, widening integers
+    pub fn get_f64(&self, name: &str) -> Option<f64> {
+        match self.values.get(name)? {
+            Some(FilterValue::Float(value)) => Some(*value),
+            Some(FilterValue::Int(value)) => Some(*value as f64),
+            _ => None,
+        }
+    }
+}
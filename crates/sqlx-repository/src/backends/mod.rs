@@ -1,15 +1,558 @@
 //! Database backend implementations
 //!
 //! This module contains database-specific implementations for different
-//! database systems. Currently only PostgreSQL is implemented, with
-//! MySQL and SQLite backends planned for future phases.
+//! database systems. PostgreSQL, MySQL/MariaDB and SQLite are implemented,
+//! each behind the Cargo feature of the same name.
+//!
+//! Each backend implements [`DatabaseBackend`], which supplies the small set of
+//! dialect differences the [`Repository`](crate::Repository) default methods
+//! need when building SQL.
+
+use crate::aggregate::AggregateFunc;
+use crate::error::{RepositoryError, RepositoryResult};
+use crate::filter::{FieldType, SqlValue};
+use crate::search::{MatchMode, NullsOrder, SortKey, SortOrder, ValueOrder};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use sqlx::types::Uuid;
+use sqlx::{Arguments, ColumnIndex, Database, Decode, Encode, Row, Type};
 
 #[cfg(feature = "postgres")]
 pub mod postgres;
 
-// Future database backends
-// #[cfg(feature = "mysql")]
-// pub mod mysql;
+#[cfg(feature = "mysql")]
+pub mod mysql;
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+#[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
+compile_error!("sqlx-repository requires at least one database backend feature: `postgres`, `mysql` or `sqlite`");
+
+/// Backend used by `#[derive(Repository)]` when no `backend` attribute is given
+///
+/// The first enabled backend in the order PostgreSQL, MySQL, SQLite.
+#[cfg(feature = "postgres")]
+pub type DefaultBackend = postgres::PostgresBackend;
+
+/// Backend used by `#[derive(Repository)]` when no `backend` attribute is given
+///
+/// The first enabled backend in the order PostgreSQL, MySQL, SQLite.
+#[cfg(all(not(feature = "postgres"), feature = "mysql"))]
+pub type DefaultBackend = mysql::MySqlBackend;
+
+/// Backend used by `#[derive(Repository)]` when no `backend` attribute is given
+///
+/// The first enabled backend in the order PostgreSQL, MySQL, SQLite.
+#[cfg(all(not(feature = "postgres"), not(feature = "mysql"), feature = "sqlite"))]
+pub type DefaultBackend = sqlite::SqliteBackend;
+
+/// Escape character in the `LIKE` patterns the repository builds
+///
+/// `!` rather than a backslash, which MySQL string literals would also
+/// interpret.
+pub const LIKE_ESCAPE: char = '!';
+
+/// Escape `value` so it matches literally inside a `LIKE` pattern
+///
+/// Prefixes `%`, `_` and [`LIKE_ESCAPE`] itself with [`LIKE_ESCAPE`]; use
+/// the result with an `ESCAPE '!'` clause.
+pub fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | LIKE_ESCAPE) {
+            escaped.push(LIKE_ESCAPE);
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// `LIKE` pattern matching `value` in `mode`, escaped with [`LIKE_ESCAPE`]
+pub fn like_pattern(value: &str, mode: MatchMode) -> String {
+    let escaped = escape_like(value);
+    match mode {
+        // Fuzzy matching compares trigrams, not patterns; as a pattern it's a substring
+        MatchMode::Contains | MatchMode::CaseSensitive | MatchMode::Fuzzy => format!("%{}%", escaped),
+        MatchMode::Prefix => format!("{}%", escaped),
+        MatchMode::Suffix => format!("%{}", escaped),
+        MatchMode::Exact => escaped,
+    }
+}
+
+/// Row estimate of the top node of a query plan, from its first `rows=N`
+///
+/// Reads PostgreSQL `EXPLAIN` and MySQL `EXPLAIN FORMAT=TREE` output, where
+/// MySQL may print fractional or exponent estimates such as `rows=1.2e+6`.
+pub fn plan_rows_estimate(plan: &str) -> Option<i64> {
+    let (_, rest) = plan.split_once("rows=")?;
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | 'e' | '+' | '-')))
+        .unwrap_or(rest.len());
+    rest[..end].parse::<f64>().ok().map(|rows| rows.round() as i64)
+}
+
+/// Bind a [`SqlValue`] as the matching Rust type
+///
+/// Shared implementation of [`DatabaseBackend::bind_value`] for any driver
+/// that can encode the integer, float, text, UUID and chrono types.
+pub fn bind_sql_value<'q, DB>(args: &mut DB::Arguments<'q>, value: &SqlValue) -> RepositoryResult<()>
+where
+    DB: Database,
+    bool: Encode<'q, DB> + Type<DB>,
+    i16: Encode<'q, DB> + Type<DB>,
+    i32: Encode<'q, DB> + Type<DB>,
+    i64: Encode<'q, DB> + Type<DB>,
+    f32: Encode<'q, DB> + Type<DB>,
+    f64: Encode<'q, DB> + Type<DB>,
+    String: Encode<'q, DB> + Type<DB>,
+    Uuid: Encode<'q, DB> + Type<DB>,
+    DateTime<Utc>: Encode<'q, DB> + Type<DB>,
+    NaiveDateTime: Encode<'q, DB> + Type<DB>,
+    NaiveDate: Encode<'q, DB> + Type<DB>,
+    NaiveTime: Encode<'q, DB> + Type<DB>,
+{
+    let result = match value {
+        SqlValue::Bool(value) => args.add(*value),
+        SqlValue::I16(value) => args.add(*value),
+        SqlValue::I32(value) => args.add(*value),
+        SqlValue::I64(value) => args.add(*value),
+        SqlValue::F32(value) => args.add(*value),
+        SqlValue::F64(value) => args.add(*value),
+        SqlValue::Text(value) => args.add(value.clone()),
+        SqlValue::Uuid(value) => args.add(*value),
+        SqlValue::DateTime(value) => args.add(*value),
+        SqlValue::NaiveDateTime(value) => args.add(*value),
+        SqlValue::NaiveDate(value) => args.add(*value),
+        SqlValue::NaiveTime(value) => args.add(*value),
+    };
+    result.map_err(|err| RepositoryError::Database(sqlx::Error::Encode(err)))
+}
+
+/// Read `column` from `row` as `field_type`
+///
+/// Shared implementation of [`DatabaseBackend::decode_value`]; `NULL`
+/// decodes as `None`.
+pub fn decode_sql_value<'r, DB>(row: &'r DB::Row, column: &str, field_type: FieldType) -> RepositoryResult<Option<SqlValue>>
+where
+    DB: Database,
+    for<'c> &'c str: ColumnIndex<DB::Row>,
+    bool: Decode<'r, DB> + Type<DB>,
+    i16: Decode<'r, DB> + Type<DB>,
+    i32: Decode<'r, DB> + Type<DB>,
+    i64: Decode<'r, DB> + Type<DB>,
+    f32: Decode<'r, DB> + Type<DB>,
+    f64: Decode<'r, DB> + Type<DB>,
+    String: Decode<'r, DB> + Type<DB>,
+    Uuid: Decode<'r, DB> + Type<DB>,
+    DateTime<Utc>: Decode<'r, DB> + Type<DB>,
+    NaiveDateTime: Decode<'r, DB> + Type<DB>,
+    NaiveDate: Decode<'r, DB> + Type<DB>,
+    NaiveTime: Decode<'r, DB> + Type<DB>,
+{
+    let value = match field_type {
+        FieldType::Bool => row.try_get::<Option<bool>, _>(column)?.map(SqlValue::Bool),
+        FieldType::I16 => row.try_get::<Option<i16>, _>(column)?.map(SqlValue::I16),
+        FieldType::I32 => row.try_get::<Option<i32>, _>(column)?.map(SqlValue::I32),
+        FieldType::I64 => row.try_get::<Option<i64>, _>(column)?.map(SqlValue::I64),
+        FieldType::F32 => row.try_get::<Option<f32>, _>(column)?.map(SqlValue::F32),
+        FieldType::F64 => row.try_get::<Option<f64>, _>(column)?.map(SqlValue::F64),
+        FieldType::Text => row.try_get::<Option<String>, _>(column)?.map(SqlValue::Text),
+        FieldType::Uuid => row.try_get::<Option<Uuid>, _>(column)?.map(SqlValue::Uuid),
+        FieldType::DateTime => row.try_get::<Option<DateTime<Utc>>, _>(column)?.map(SqlValue::DateTime),
+        FieldType::NaiveDateTime => row.try_get::<Option<NaiveDateTime>, _>(column)?.map(SqlValue::NaiveDateTime),
+        FieldType::NaiveDate => row.try_get::<Option<NaiveDate>, _>(column)?.map(SqlValue::NaiveDate),
+        FieldType::NaiveTime => row.try_get::<Option<NaiveTime>, _>(column)?.map(SqlValue::NaiveTime),
+    };
+    Ok(value)
+}
+
+/// SQL dialect of a supported database
+///
+/// Backends implement the handful of dialect primitives (placeholders,
+/// identifier quoting, `RETURNING`, the current timestamp, case-insensitive
+/// matching and type mapping). The `*_sql` builders are provided on top of
+/// them, so every statement the repository and the derive macro run is built
+/// here.
+pub trait DatabaseBackend: Send + Sync + 'static {
+    /// The sqlx database driver for this backend
+    type Database: sqlx::Database;
+
+    /// Human readable backend name used in error messages
+    const NAME: &'static str;
+
+    /// Whether `INSERT`/`UPDATE ... RETURNING *` is available
+    ///
+    /// Backends without it re-select the written row by id.
+    const SUPPORTS_RETURNING: bool = true;
+
+    /// Character used to quote identifiers
+    const IDENTIFIER_QUOTE: char = '"';
+
+    /// Whether `NULL` sorts after every other value in ascending order
+    ///
+    /// PostgreSQL treats `NULL` as the largest value; MySQL and SQLite treat
+    /// it as the smallest. Keyset pagination needs this to page past `NULL`s
+    /// when a sort key leaves their placement to the database.
+    const NULLS_SORT_LAST: bool = true;
+
+    /// Type integer `SUM`s are cast to so they read back as `i64`
+    const BIGINT_CAST: &'static str = "BIGINT";
+
+    /// Type `AVG`s and floating-point `SUM`s are cast to so they read back as `f64`
+    const DOUBLE_CAST: &'static str = "DOUBLE PRECISION";
+
+    /// Placeholder for the bind parameter at `index` (1-based)
+    fn placeholder(index: usize) -> String;
+
+    /// SQL expression evaluating to the current timestamp
+    fn now() -> &'static str;
+
+    /// Case-insensitive pattern match of `column` against `placeholder`
+    fn case_insensitive_like(column: &str, placeholder: &str) -> String;
+
+    /// Pattern to bind for matching text against `value` in `mode`
+    ///
+    /// The value is escaped, so its `%` and `_` match literally.
+    fn match_pattern(value: &str, mode: MatchMode) -> String {
+        like_pattern(value, mode)
+    }
+
+    /// Condition matching `column` against a [`match_pattern`](Self::match_pattern) bound at `placeholder`
+    fn match_sql(column: &str, placeholder: &str, mode: MatchMode) -> String {
+        match mode {
+            MatchMode::CaseSensitive => format!("{} LIKE {} ESCAPE '{}'", column, placeholder, LIKE_ESCAPE),
+            _ => format!("{} ESCAPE '{}'", Self::case_insensitive_like(column, placeholder), LIKE_ESCAPE),
+        }
+    }
+
+    /// Full-text condition matching `columns` against the search query bound at `placeholder`
+    ///
+    /// `config` is the text search configuration, such as `english`. Only
+    /// PostgreSQL implements full-text search.
+    fn full_text_match_sql(_config: &str, _columns: &[&str], _placeholder: &str) -> RepositoryResult<String> {
+        Err(RepositoryError::unsupported_feature("full_text", Self::NAME))
+    }
+
+    /// Relevance of `columns` to the search query bound at `placeholder`, higher is better
+    fn full_text_rank_sql(_config: &str, _columns: &[&str], _placeholder: &str) -> RepositoryResult<String> {
+        Err(RepositoryError::unsupported_feature("full_text", Self::NAME))
+    }
+
+    /// Trigram similarity condition on any of `columns` for the query bound at `placeholder`
+    ///
+    /// Without a `threshold` placeholder the server's default threshold
+    /// applies. Only PostgreSQL with the `pg_trgm` extension implements this.
+    fn similarity_match_sql(_columns: &[&str], _placeholder: &str, _threshold: Option<&str>) -> RepositoryResult<String> {
+        Err(RepositoryError::unsupported_feature("fuzzy_search", Self::NAME))
+    }
+
+    /// Best trigram similarity of `columns` to the query bound at `placeholder`, from 0 to 1
+    fn similarity_score_sql(_columns: &[&str], _placeholder: &str) -> RepositoryResult<String> {
+        Err(RepositoryError::unsupported_feature("fuzzy_search", Self::NAME))
+    }
+
+    /// Query returning how many of the extensions fuzzy search needs are installed
+    ///
+    /// `None` when the backend has no fuzzy search at all.
+    fn fuzzy_search_check_sql() -> Option<&'static str> {
+        None
+    }
+
+    /// Snippet of `column` highlighting the terms of the search query bound at `placeholder`
+    fn full_text_headline_sql(_config: &str, _column: &str, _placeholder: &str) -> RepositoryResult<String> {
+        Err(RepositoryError::unsupported_feature("full_text", Self::NAME))
+    }
+
+    /// Column type used for a Rust type name such as `"i64"` or `"DateTime<Utc>"`
+    fn convert_type(rust_type: &str) -> &'static str;
+
+    /// Append a parsed filter value to `args` as a bind parameter
+    ///
+    /// Implementations usually forward to [`bind_sql_value`].
+    fn bind_value<'q>(
+        args: &mut <Self::Database as Database>::Arguments<'q>,
+        value: &SqlValue,
+    ) -> RepositoryResult<()>;
+
+    /// Read `column` from a result row as `field_type`, `None` for `NULL`
+    ///
+    /// Implementations usually forward to [`decode_sql_value`].
+    fn decode_value(
+        row: &<Self::Database as Database>::Row,
+        column: &str,
+        field_type: FieldType,
+    ) -> RepositoryResult<Option<SqlValue>>;
+
+    /// Number of rows affected by an executed statement
+    fn rows_affected(result: &<Self::Database as sqlx::Database>::QueryResult) -> u64;
+
+    /// Auto-increment id generated by an executed `INSERT`, if the backend reports one
+    fn last_insert_id(_result: &<Self::Database as sqlx::Database>::QueryResult) -> Option<u64> {
+        None
+    }
+
+    /// Quote a table or column name
+    ///
+    /// Each part of a dotted `schema.table` name is quoted separately and
+    /// embedded quote characters are doubled.
+    fn quote_identifier(ident: &str) -> String {
+        let quote = Self::IDENTIFIER_QUOTE;
+        let escaped = format!("{quote}{quote}");
+        ident
+            .split('.')
+            .map(|part| format!("{quote}{}{quote}", part.replace(quote, &escaped)))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// `SELECT` a single row by id, skipping soft-deleted rows when `soft_delete` is set
+    fn select_by_id_sql(table: &str, soft_delete: bool) -> String {
+        let mut query = format!(
+            "SELECT * FROM {} WHERE {} = {}",
+            Self::quote_identifier(table),
+            Self::quote_identifier("id"),
+            Self::placeholder(1)
+        );
+        if soft_delete {
+            query.push_str(&format!(" AND {} IS NULL", Self::quote_identifier("deleted_at")));
+        }
+        query
+    }
+
+    /// `SELECT` every row ordered by id, skipping soft-deleted rows when `soft_delete` is set
+    fn select_all_sql(table: &str, soft_delete: bool) -> String {
+        let mut query = format!("SELECT * FROM {}", Self::quote_identifier(table));
+        if soft_delete {
+            query.push_str(&format!(" WHERE {} IS NULL", Self::quote_identifier("deleted_at")));
+        }
+        query.push_str(&format!(" ORDER BY {}", Self::quote_identifier("id")));
+        query
+    }
+
+    /// `INSERT` a row binding `columns` in order
+    ///
+    /// With `timestamps` set, `created_at` and `updated_at` are filled from
+    /// [`now`](Self::now). Ends in `RETURNING *` when the backend supports it.
+    fn insert_sql(table: &str, columns: &[&str], timestamps: bool) -> String {
+        let mut names: Vec<String> = columns.iter().map(|c| Self::quote_identifier(c)).collect();
+        let mut values: Vec<String> = (1..=columns.len()).map(Self::placeholder).collect();
+
+        if timestamps {
+            for column in ["created_at", "updated_at"] {
+                names.push(Self::quote_identifier(column));
+                values.push(Self::now().to_string());
+            }
+        }
+
+        let mut query = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            Self::quote_identifier(table),
+            names.join(", "),
+            values.join(", ")
+        );
+        if Self::SUPPORTS_RETURNING {
+            query.push_str(" RETURNING *");
+        }
+        query
+    }
+
+    /// `UPDATE` a row by id binding `columns` in order, then the id
+    ///
+    /// With `soft_delete` set, `updated_at` is refreshed and soft-deleted rows
+    /// are left untouched. Ends in `RETURNING *` when the backend supports it.
+    fn update_sql(table: &str, columns: &[&str], soft_delete: bool) -> String {
+        let mut set_parts: Vec<String> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| format!("{} = {}", Self::quote_identifier(column), Self::placeholder(i + 1)))
+            .collect();
+        if soft_delete {
+            set_parts.push(format!("{} = {}", Self::quote_identifier("updated_at"), Self::now()));
+        }
+
+        let mut query = format!(
+            "UPDATE {} SET {} WHERE {} = {}",
+            Self::quote_identifier(table),
+            set_parts.join(", "),
+            Self::quote_identifier("id"),
+            Self::placeholder(columns.len() + 1)
+        );
+        if soft_delete {
+            query.push_str(&format!(" AND {} IS NULL", Self::quote_identifier("deleted_at")));
+        }
+        if Self::SUPPORTS_RETURNING {
+            query.push_str(" RETURNING *");
+        }
+        query
+    }
+
+    /// Delete a row by id: a soft delete when `soft_delete` is set, otherwise a hard `DELETE`
+    fn delete_sql(table: &str, soft_delete: bool) -> String {
+        if soft_delete {
+            format!(
+                "UPDATE {} SET {deleted_at} = {now}, {} = {now} WHERE {} = {} AND {deleted_at} IS NULL",
+                Self::quote_identifier(table),
+                Self::quote_identifier("updated_at"),
+                Self::quote_identifier("id"),
+                Self::placeholder(1),
+                deleted_at = Self::quote_identifier("deleted_at"),
+                now = Self::now()
+            )
+        } else {
+            format!(
+                "DELETE FROM {} WHERE {} = {}",
+                Self::quote_identifier(table),
+                Self::quote_identifier("id"),
+                Self::placeholder(1)
+            )
+        }
+    }
+
+    /// Clear `deleted_at` on a row by id
+    ///
+    /// Ends in `RETURNING *` when the backend supports it.
+    fn restore_sql(table: &str) -> String {
+        let mut query = format!(
+            "UPDATE {} SET {} = NULL, {} = {} WHERE {} = {}",
+            Self::quote_identifier(table),
+            Self::quote_identifier("deleted_at"),
+            Self::quote_identifier("updated_at"),
+            Self::now(),
+            Self::quote_identifier("id"),
+            Self::placeholder(1)
+        );
+        if Self::SUPPORTS_RETURNING {
+            query.push_str(" RETURNING *");
+        }
+        query
+    }
+
+    /// `SELECT COUNT(*)` with a prebuilt `where_clause` (empty or starting with ` WHERE`)
+    fn count_sql(table: &str, where_clause: &str) -> String {
+        format!("SELECT COUNT(*) FROM {}{}", Self::quote_identifier(table), where_clause)
+    }
+
+    /// Number of rows matching a prebuilt `where_clause` per value of `field`, most common first
+    ///
+    /// Selects the value as `value` and its count as `count`.
+    fn facet_sql(table: &str, field: &str, where_clause: &str) -> String {
+        Self::distinct_values_sql(table, field, where_clause, ValueOrder::Frequency, None, 0)
+    }
+
+    /// Distinct values of `field` among the rows matching a prebuilt `where_clause`, with their counts
+    ///
+    /// Selects the value as `value` and its count as `count`, in `order`,
+    /// skipping `offset` values and returning at most `limit`.
+    fn distinct_values_sql(
+        table: &str,
+        field: &str,
+        where_clause: &str,
+        order: ValueOrder,
+        limit: Option<u32>,
+        offset: u32,
+    ) -> String {
+        let column = Self::quote_identifier(field);
+        let order_by = match order {
+            ValueOrder::Frequency => format!("COUNT(*) DESC, {}", column),
+            ValueOrder::Alphabetical => column.clone(),
+        };
+        let mut query = format!(
+            "SELECT {} AS {}, COUNT(*) AS {} FROM {}{} GROUP BY {} ORDER BY {}",
+            column,
+            Self::quote_identifier("value"),
+            Self::quote_identifier("count"),
+            Self::quote_identifier(table),
+            where_clause,
+            column,
+            order_by
+        );
+        if let Some(limit) = limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+            if offset > 0 {
+                query.push_str(&format!(" OFFSET {}", offset));
+            }
+        }
+        query
+    }
+
+    /// `func` applied to `column`, or `COUNT(*)` without one, cast to the
+    /// function's [`AggregateFunc::result_type`] for a column of `field_type`
+    fn aggregate_term_sql(func: AggregateFunc, column: Option<&str>, field_type: FieldType) -> String {
+        let Some(column) = column else {
+            return "COUNT(*)".to_string();
+        };
+        let call = format!("{}({})", func.to_string().to_uppercase(), Self::quote_identifier(column));
+        match (func, func.result_type(field_type)) {
+            (AggregateFunc::Sum | AggregateFunc::Avg, FieldType::F64) => format!("CAST({} AS {})", call, Self::DOUBLE_CAST),
+            (AggregateFunc::Sum, _) => format!("CAST({} AS {})", call, Self::BIGINT_CAST),
+            _ => call,
+        }
+    }
+
+    /// `SELECT` of prebuilt `columns` over the rows matching `where_clause`,
+    /// grouped and ordered by `group_by`
+    fn aggregate_sql(table: &str, columns: &[String], where_clause: &str, group_by: &[String]) -> String {
+        let mut sql = format!("SELECT {} FROM {}{}", columns.join(", "), Self::quote_identifier(table), where_clause);
+        if !group_by.is_empty() {
+            let group_by: Vec<String> = group_by.iter().map(|field| Self::quote_identifier(field)).collect();
+            sql.push_str(&format!(" GROUP BY {} ORDER BY {}", group_by.join(", "), group_by.join(", ")));
+        }
+        sql
+    }
+
+    /// Query plan for the rows matching a prebuilt `where_clause`
+    ///
+    /// The first column of the first row must hold the plan text, whose first
+    /// `rows=N` is the planner's estimate (see [`plan_rows_estimate`]).
+    fn estimated_count_sql(_table: &str, _where_clause: &str) -> RepositoryResult<String> {
+        Err(RepositoryError::unsupported_feature("estimated_count", Self::NAME))
+    }
+
+    /// `ORDER BY` term for one sort key
+    ///
+    /// Explicit `NULL` placement uses `NULLS FIRST` / `NULLS LAST`.
+    fn order_by_sql(key: &SortKey) -> String {
+        let direction = match key.order {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        };
+        let nulls = match key.nulls {
+            NullsOrder::Default => "",
+            NullsOrder::First => " NULLS FIRST",
+            NullsOrder::Last => " NULLS LAST",
+        };
+        format!("{} {}{}", Self::quote_identifier(&key.field), direction, nulls)
+    }
 
-// #[cfg(feature = "sqlite")]
-// pub mod sqlite;
\ No newline at end of file
+    /// Rows matching a prebuilt `where_clause`, selecting `extra_columns` after
+    /// every table column and ordering by prebuilt `ORDER BY` terms
+    ///
+    /// Without an `offset` the page starts at the first matching row, as
+    /// keyset pagination expects.
+    fn select_page_sql(
+        table: &str,
+        extra_columns: &[String],
+        where_clause: &str,
+        order_by: &[String],
+        limit: u32,
+        offset: Option<u32>,
+    ) -> String {
+        let mut columns = String::from("*");
+        for column in extra_columns {
+            columns.push_str(", ");
+            columns.push_str(column);
+        }
+        let mut query = format!(
+            "SELECT {} FROM {}{} ORDER BY {} LIMIT {}",
+            columns,
+            Self::quote_identifier(table),
+            where_clause,
+            order_by.join(", "),
+            limit
+        );
+        if let Some(offset) = offset {
+            query.push_str(&format!(" OFFSET {}", offset));
+        }
+        query
+    }
+}
@@ -0,0 +1,123 @@
+//! MySQL/MariaDB-specific implementations
+//!
+//! This module contains MySQL-specific functionality used by the
+//! repository implementations through the [`DatabaseBackend`] trait.
+//!
+//! MySQL only understands positional `?` parameters and has no `RETURNING`
+//! clause, so repositories on this backend re-select a row after writing it,
+//! using the `LAST_INSERT_ID()` value reported by the server for inserts.
+
+use super::{bind_sql_value, decode_sql_value, DatabaseBackend, LIKE_ESCAPE};
+use crate::error::RepositoryResult;
+use crate::filter::{FieldType, SqlValue};
+use crate::search::{MatchMode, NullsOrder, SortKey, SortOrder};
+
+/// MySQL-specific query helpers
+pub struct MySqlBackend;
+
+impl MySqlBackend {
+    /// Create a MySQL-specific placeholder for parameter binding
+    ///
+    /// MySQL placeholders are positional, so the index is ignored.
+    pub fn placeholder(_index: usize) -> String {
+        "?".to_string()
+    }
+
+    /// Convert Rust types to MySQL column types
+    pub fn convert_type(rust_type: &str) -> &'static str {
+        match rust_type {
+            "i16" => "SMALLINT",
+            "i32" => "INT",
+            "i64" => "BIGINT",
+            "String" => "VARCHAR(255)",
+            "bool" => "BOOLEAN",
+            "DateTime<Utc>" => "TIMESTAMP(6)",
+            "NaiveDateTime" => "DATETIME(6)",
+            "NaiveDate" => "DATE",
+            "NaiveTime" => "TIME",
+            "Decimal" => "DECIMAL",
+            "f32" => "FLOAT",
+            "f64" => "DOUBLE",
+            "Uuid" => "BINARY(16)",
+            _ => "VARCHAR(255)", // Safe default
+        }
+    }
+}
+
+impl DatabaseBackend for MySqlBackend {
+    type Database = sqlx::MySql;
+
+    const NAME: &'static str = "mysql";
+
+    const NULLS_SORT_LAST: bool = false;
+
+    const SUPPORTS_RETURNING: bool = false;
+
+    const IDENTIFIER_QUOTE: char = '`';
+
+    const BIGINT_CAST: &'static str = "SIGNED";
+
+    const DOUBLE_CAST: &'static str = "DOUBLE";
+
+    fn placeholder(index: usize) -> String {
+        MySqlBackend::placeholder(index)
+    }
+
+    fn convert_type(rust_type: &str) -> &'static str {
+        MySqlBackend::convert_type(rust_type)
+    }
+
+    fn estimated_count_sql(table: &str, where_clause: &str) -> RepositoryResult<String> {
+        // Tree plans need MySQL 8.0.16 or later; MariaDB rejects `FORMAT=TREE`
+        Ok(format!("EXPLAIN FORMAT=TREE SELECT * FROM {}{}", Self::quote_identifier(table), where_clause))
+    }
+
+    fn order_by_sql(key: &SortKey) -> String {
+        let column = Self::quote_identifier(&key.field);
+        let direction = match key.order {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        };
+        // MySQL has no NULLS FIRST / LAST; sort on `IS NULL` first instead
+        match key.nulls {
+            NullsOrder::Default => format!("{} {}", column, direction),
+            NullsOrder::First => format!("{} IS NULL DESC, {} {}", column, column, direction),
+            NullsOrder::Last => format!("{} IS NULL ASC, {} {}", column, column, direction),
+        }
+    }
+
+    fn now() -> &'static str {
+        "CURRENT_TIMESTAMP"
+    }
+
+    fn case_insensitive_like(column: &str, placeholder: &str) -> String {
+        // Lower both sides so the match does not depend on the column collation
+        format!("LOWER({}) LIKE LOWER({})", column, placeholder)
+    }
+
+    fn match_sql(column: &str, placeholder: &str, mode: MatchMode) -> String {
+        match mode {
+            // Compare bytes, since the default collations ignore case
+            MatchMode::CaseSensitive => {
+                format!("CAST({} AS BINARY) LIKE {} ESCAPE '{}'", column, placeholder, LIKE_ESCAPE)
+            }
+            _ => format!("{} ESCAPE '{}'", Self::case_insensitive_like(column, placeholder), LIKE_ESCAPE),
+        }
+    }
+
+    fn bind_value<'q>(args: &mut <sqlx::MySql as sqlx::Database>::Arguments<'q>, value: &SqlValue) -> RepositoryResult<()> {
+        bind_sql_value::<sqlx::MySql>(args, value)
+    }
+
+    fn decode_value(row: &<sqlx::MySql as sqlx::Database>::Row, column: &str, field_type: FieldType) -> RepositoryResult<Option<SqlValue>> {
+        decode_sql_value::<sqlx::MySql>(row, column, field_type)
+    }
+
+    fn rows_affected(result: &sqlx::mysql::MySqlQueryResult) -> u64 {
+        result.rows_affected()
+    }
+
+    fn last_insert_id(result: &sqlx::mysql::MySqlQueryResult) -> Option<u64> {
+        Some(result.last_insert_id())
+    }
+}
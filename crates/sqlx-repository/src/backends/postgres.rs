@@ -1,9 +1,11 @@
 //! PostgreSQL-specific implementations
 //!
-//! This module contains PostgreSQL-specific functionality that will be
-//! used by the repository implementations. In Phase 2, this will be
-//! refactored to implement a common DatabaseBackend trait.
+//! This module contains PostgreSQL-specific functionality used by the
+//! repository implementations through the [`DatabaseBackend`] trait.
 
+use super::{bind_sql_value, decode_sql_value, DatabaseBackend};
+use crate::error::RepositoryResult;
+use crate::filter::{FieldType, SqlValue};
 
 /// PostgreSQL-specific query helpers
 pub struct PostgresBackend;
@@ -15,8 +17,9 @@ impl PostgresBackend {
     }
     
     /// Convert Rust types to PostgreSQL types
-    pub fn convert_type(rust_type: &str) -> &str {
+    pub fn convert_type(rust_type: &str) -> &'static str {
         match rust_type {
+            "i16" => "SMALLINT",
             "i32" => "INTEGER",
             "i64" => "BIGINT",
             "String" => "VARCHAR",
@@ -28,6 +31,7 @@ impl PostgresBackend {
             "Decimal" => "DECIMAL",
             "f32" => "REAL",
             "f64" => "DOUBLE PRECISION",
+            "Uuid" => "UUID",
             _ => "VARCHAR", // Safe default
         }
     }
@@ -119,4 +123,105 @@ impl PostgresBackend {
             format!("DELETE FROM {} WHERE id = $1", table)
         }
     }
-}
\ No newline at end of file
+}
+
+/// Text search configuration as a string literal
+fn ts_config(config: &str) -> String {
+    format!("'{}'", config.replace('\'', "''"))
+}
+
+/// `tsvector` of `columns` joined by spaces
+///
+/// A GIN index on exactly this expression lets PostgreSQL use it for searches.
+fn ts_document(config: &str, columns: &[&str]) -> String {
+    let document: Vec<String> = columns
+        .iter()
+        .map(|column| format!("coalesce({}, '')", PostgresBackend::quote_identifier(column)))
+        .collect();
+    format!("to_tsvector({}, {})", ts_config(config), document.join(" || ' ' || "))
+}
+
+/// `tsquery` parsed from web-search syntax (`"quoted phrases"`, `or`, `-excluded`)
+fn ts_query(config: &str, placeholder: &str) -> String {
+    format!("websearch_to_tsquery({}, {})", ts_config(config), placeholder)
+}
+
+impl DatabaseBackend for PostgresBackend {
+    type Database = sqlx::Postgres;
+
+    const NAME: &'static str = "postgres";
+
+    fn placeholder(index: usize) -> String {
+        PostgresBackend::placeholder(index)
+    }
+
+    fn convert_type(rust_type: &str) -> &'static str {
+        PostgresBackend::convert_type(rust_type)
+    }
+
+    fn now() -> &'static str {
+        "NOW()"
+    }
+
+    fn case_insensitive_like(column: &str, placeholder: &str) -> String {
+        format!("{} ILIKE {}", column, placeholder)
+    }
+
+    fn full_text_match_sql(config: &str, columns: &[&str], placeholder: &str) -> RepositoryResult<String> {
+        Ok(format!("{} @@ {}", ts_document(config, columns), ts_query(config, placeholder)))
+    }
+
+    fn full_text_rank_sql(config: &str, columns: &[&str], placeholder: &str) -> RepositoryResult<String> {
+        Ok(format!("ts_rank({}, {})", ts_document(config, columns), ts_query(config, placeholder)))
+    }
+
+    fn estimated_count_sql(table: &str, where_clause: &str) -> RepositoryResult<String> {
+        Ok(format!("EXPLAIN SELECT * FROM {}{}", Self::quote_identifier(table), where_clause))
+    }
+
+    fn similarity_match_sql(columns: &[&str], placeholder: &str, threshold: Option<&str>) -> RepositoryResult<String> {
+        match threshold {
+            Some(threshold) => Ok(format!("{} >= {}", Self::similarity_score_sql(columns, placeholder)?, threshold)),
+            None => {
+                let matches: Vec<String> = columns
+                    .iter()
+                    .map(|column| format!("{} % {}", Self::quote_identifier(column), placeholder))
+                    .collect();
+                Ok(format!("({})", matches.join(" OR ")))
+            }
+        }
+    }
+
+    fn similarity_score_sql(columns: &[&str], placeholder: &str) -> RepositoryResult<String> {
+        let scores: Vec<String> = columns
+            .iter()
+            .map(|column| format!("similarity({}, {})", Self::quote_identifier(column), placeholder))
+            .collect();
+        Ok(format!("greatest({})", scores.join(", ")))
+    }
+
+    fn fuzzy_search_check_sql() -> Option<&'static str> {
+        Some("SELECT COUNT(*) FROM pg_extension WHERE extname = 'pg_trgm'")
+    }
+
+    fn full_text_headline_sql(config: &str, column: &str, placeholder: &str) -> RepositoryResult<String> {
+        Ok(format!(
+            "ts_headline({}, coalesce({}, ''), {})",
+            ts_config(config),
+            Self::quote_identifier(column),
+            ts_query(config, placeholder)
+        ))
+    }
+
+    fn bind_value<'q>(args: &mut <sqlx::Postgres as sqlx::Database>::Arguments<'q>, value: &SqlValue) -> RepositoryResult<()> {
+        bind_sql_value::<sqlx::Postgres>(args, value)
+    }
+
+    fn decode_value(row: &<sqlx::Postgres as sqlx::Database>::Row, column: &str, field_type: FieldType) -> RepositoryResult<Option<SqlValue>> {
+        decode_sql_value::<sqlx::Postgres>(row, column, field_type)
+    }
+
+    fn rows_affected(result: &sqlx::postgres::PgQueryResult) -> u64 {
+        result.rows_affected()
+    }
+}
@@ -0,0 +1,110 @@
+//! SQLite-specific implementations
+//!
+//! This module contains SQLite-specific functionality used by the
+//! repository implementations through the [`DatabaseBackend`] trait.
+//!
+//! SQLite differs from PostgreSQL in a few places the repository cares about:
+//! numbered parameters are written `?N`, `LIKE` is already case-insensitive
+//! for ASCII text, and timestamps are stored as ISO-8601 text.
+
+use super::{bind_sql_value, decode_sql_value, like_pattern, DatabaseBackend, LIKE_ESCAPE};
+use crate::error::RepositoryResult;
+use crate::filter::{FieldType, SqlValue};
+use crate::search::MatchMode;
+
+/// SQLite-specific query helpers
+pub struct SqliteBackend;
+
+impl SqliteBackend {
+    /// Create a SQLite-specific placeholder for parameter binding
+    pub fn placeholder(index: usize) -> String {
+        format!("?{}", index)
+    }
+
+    /// Convert Rust types to SQLite column types
+    pub fn convert_type(rust_type: &str) -> &'static str {
+        match rust_type {
+            "i16" | "i32" | "i64" | "u16" | "u32" | "bool" => "INTEGER",
+            "String" => "TEXT",
+            "DateTime<Utc>" | "NaiveDateTime" | "NaiveDate" | "NaiveTime" => "TEXT",
+            "Decimal" => "NUMERIC",
+            "f32" | "f64" => "REAL",
+            "Uuid" => "BLOB",
+            _ => "TEXT", // Safe default
+        }
+    }
+}
+
+impl DatabaseBackend for SqliteBackend {
+    type Database = sqlx::Sqlite;
+
+    const NAME: &'static str = "sqlite";
+
+    const NULLS_SORT_LAST: bool = false;
+
+    const BIGINT_CAST: &'static str = "INTEGER";
+
+    const DOUBLE_CAST: &'static str = "REAL";
+
+    fn placeholder(index: usize) -> String {
+        SqliteBackend::placeholder(index)
+    }
+
+    fn convert_type(rust_type: &str) -> &'static str {
+        SqliteBackend::convert_type(rust_type)
+    }
+
+    fn now() -> &'static str {
+        // RFC 3339 in UTC, the way sqlx encodes `DateTime<Utc>` (no fraction
+        // for whole seconds, `+00:00` offset), so timestamps written here and
+        // bound from Rust compare correctly as text
+        "replace(strftime('%Y-%m-%dT%H:%M:%f', 'now'), '.000', '') || '+00:00'"
+    }
+
+    fn case_insensitive_like(column: &str, placeholder: &str) -> String {
+        // SQLite's LIKE is case-insensitive for ASCII characters by default
+        format!("{} LIKE {}", column, placeholder)
+    }
+
+    fn match_pattern(value: &str, mode: MatchMode) -> String {
+        match mode {
+            // GLOB is SQLite's case-sensitive LIKE, with `*` and `?` as wildcards
+            // and brackets to match them literally
+            MatchMode::CaseSensitive => {
+                let mut pattern = String::from("*");
+                for c in value.chars() {
+                    match c {
+                        '*' | '?' | '[' => pattern.extend(['[', c, ']']),
+                        _ => pattern.push(c),
+                    }
+                }
+                pattern.push('*');
+                pattern
+            }
+            _ => like_pattern(value, mode),
+        }
+    }
+
+    fn match_sql(column: &str, placeholder: &str, mode: MatchMode) -> String {
+        match mode {
+            MatchMode::CaseSensitive => format!("{} GLOB {}", column, placeholder),
+            _ => format!("{} LIKE {} ESCAPE '{}'", column, placeholder, LIKE_ESCAPE),
+        }
+    }
+
+    fn bind_value<'q>(args: &mut <sqlx::Sqlite as sqlx::Database>::Arguments<'q>, value: &SqlValue) -> RepositoryResult<()> {
+        bind_sql_value::<sqlx::Sqlite>(args, value)
+    }
+
+    fn decode_value(row: &<sqlx::Sqlite as sqlx::Database>::Row, column: &str, field_type: FieldType) -> RepositoryResult<Option<SqlValue>> {
+        decode_sql_value::<sqlx::Sqlite>(row, column, field_type)
+    }
+
+    fn rows_affected(result: &sqlx::sqlite::SqliteQueryResult) -> u64 {
+        result.rows_affected()
+    }
+
+    fn last_insert_id(result: &sqlx::sqlite::SqliteQueryResult) -> Option<u64> {
+        u64::try_from(result.last_insert_rowid()).ok()
+    }
+}
@@ -0,0 +1,206 @@
+//! Keyset (cursor) pagination
+//!
+//! A cursor records the sort key and id of the row a page ended (or began)
+//! on. The next query seeks past that row with a `WHERE` condition instead of
+//! an `OFFSET`, so paging stays fast on large tables and rows inserted or
+//! deleted between requests don't shift later pages.
+//!
+//! Cursors are opaque to clients: a JSON payload and an HMAC-SHA256 tag,
+//! both base64url encoded. A cursor that was altered, or issued for another
+//! table or sort order, is rejected with [`RepositoryError::Validation`].
+//!
+//! The signing key is random per process unless set with
+//! [`set_cursor_secret`]. Set it at startup when cursors must stay valid
+//! across restarts or several application instances.
+
+use crate::backends::DatabaseBackend;
+use crate::error::{RepositoryError, RepositoryResult};
+use crate::filter::{field_type_of, FieldType, FilterValue, SqlValue};
+use crate::search::{NullsOrder, SortKey, SortOrder};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::Database;
+use std::sync::{OnceLock, RwLock};
+
+type HmacSha256 = Hmac<Sha256>;
+
+static CURSOR_SECRET: OnceLock<RwLock<Vec<u8>>> = OnceLock::new();
+
+fn secret() -> &'static RwLock<Vec<u8>> {
+    CURSOR_SECRET.get_or_init(|| {
+        let mut key = vec![0u8; 32];
+        getrandom::getrandom(&mut key).expect("the OS random number generator is unavailable");
+        RwLock::new(key)
+    })
+}
+
+/// Set the key used to sign and verify pagination cursors
+///
+/// Cursors signed with a previous key stop validating.
+pub fn set_cursor_secret(key: impl Into<Vec<u8>>) {
+    *secret().write().unwrap_or_else(|err| err.into_inner()) = key.into();
+}
+
+fn mac() -> HmacSha256 {
+    let key = secret().read().unwrap_or_else(|err| err.into_inner());
+    HmacSha256::new_from_slice(&key).expect("HMAC accepts keys of any length")
+}
+
+/// Decoded cursor contents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Cursor {
+    /// Table the cursor was issued for
+    #[serde(rename = "t")]
+    table: String,
+    /// Sort keys the page was ordered by, tie-breaking id last
+    #[serde(rename = "s")]
+    sort: Vec<SortKey>,
+    /// Value of each sort key on the boundary row
+    #[serde(rename = "k")]
+    key: Vec<Option<FilterValue>>,
+    /// Page backwards from the boundary row instead of forwards
+    #[serde(rename = "b", default)]
+    before: bool,
+}
+
+impl Cursor {
+    /// Sign and encode the cursor
+    pub(crate) fn encode(&self) -> String {
+        let payload = serde_json::to_vec(self).expect("cursor payload serializes");
+        let mut mac = mac();
+        mac.update(&payload);
+        format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(&payload),
+            URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+        )
+    }
+
+    /// Verify and decode a cursor issued for `table` with the given sort keys
+    pub(crate) fn decode(cursor: &str, table: &str, sort: &[SortKey]) -> RepositoryResult<Self> {
+        let invalid = || RepositoryError::validation("Invalid pagination cursor");
+
+        let (payload, tag) = cursor.split_once('.').ok_or_else(invalid)?;
+        let payload = URL_SAFE_NO_PAD.decode(payload).map_err(|_| invalid())?;
+        let tag = URL_SAFE_NO_PAD.decode(tag).map_err(|_| invalid())?;
+
+        let mut mac = mac();
+        mac.update(&payload);
+        mac.verify_slice(&tag).map_err(|_| invalid())?;
+
+        let cursor: Cursor = serde_json::from_slice(&payload).map_err(|_| invalid())?;
+        if cursor.table != table || cursor.sort != sort || cursor.key.len() != sort.len() {
+            return Err(RepositoryError::validation(
+                "Pagination cursor does not match the requested table or sort order",
+            ));
+        }
+        Ok(cursor)
+    }
+
+    /// Whether the cursor pages backwards
+    pub(crate) fn is_before(&self) -> bool {
+        self.before
+    }
+
+    /// Keyset condition selecting the rows past the boundary row
+    ///
+    /// Compares the sort keys lexicographically in the direction of travel,
+    /// placing `NULL`s where each key or else the backend sorts them.
+    pub(crate) fn condition<B: DatabaseBackend>(
+        &self,
+        field_types: &[(&str, FieldType)],
+        bind_values: &mut Vec<SqlValue>,
+    ) -> RepositoryResult<String> {
+        let key = self
+            .sort
+            .iter()
+            .zip(&self.key)
+            .map(|(sort_key, value)| {
+                value
+                    .as_ref()
+                    .map(|value| field_type_of(field_types, &sort_key.field).parse(&sort_key.field, value))
+                    .transpose()
+            })
+            .collect::<RepositoryResult<Vec<_>>>()?;
+
+        let mut terms = Vec::new();
+        for (position, (sort_key, value)) in self.sort.iter().zip(&key).enumerate() {
+            let ascending = (sort_key.order == SortOrder::Asc) != self.before;
+            let nulls_last = match sort_key.nulls {
+                NullsOrder::Default => B::NULLS_SORT_LAST == (sort_key.order == SortOrder::Asc),
+                NullsOrder::First => false,
+                NullsOrder::Last => true,
+            };
+            let nulls_after = nulls_last != self.before;
+            let quoted = B::quote_identifier(&sort_key.field);
+
+            // Rows strictly after the boundary on this key
+            let after = match value {
+                Some(value) => {
+                    bind_values.push(value.clone());
+                    let comparison = format!(
+                        "{} {} {}",
+                        quoted,
+                        if ascending { ">" } else { "<" },
+                        B::placeholder(bind_values.len())
+                    );
+                    if nulls_after {
+                        format!("({} OR {} IS NULL)", comparison, quoted)
+                    } else {
+                        comparison
+                    }
+                }
+                None if nulls_after => continue,
+                None => format!("{} IS NOT NULL", quoted),
+            };
+
+            // ...and equal to it on every earlier key
+            let mut parts = Vec::with_capacity(position + 1);
+            for (earlier, earlier_value) in self.sort.iter().zip(&key).take(position) {
+                let earlier = B::quote_identifier(&earlier.field);
+                match earlier_value {
+                    Some(value) => {
+                        bind_values.push(value.clone());
+                        parts.push(format!("{} = {}", earlier, B::placeholder(bind_values.len())));
+                    }
+                    None => parts.push(format!("{} IS NULL", earlier)),
+                }
+            }
+            parts.push(after);
+            terms.push(format!("({})", parts.join(" AND ")));
+        }
+
+        if terms.is_empty() {
+            return Ok("1 = 0".to_string());
+        }
+        Ok(format!("({})", terms.join(" OR ")))
+    }
+}
+
+/// Build cursors pointing at `row` for paging forwards or backwards
+pub(crate) fn cursor_for_row<B: DatabaseBackend>(
+    row: &<B::Database as Database>::Row,
+    table: &str,
+    sort: &[SortKey],
+    field_types: &[(&str, FieldType)],
+    before: bool,
+) -> RepositoryResult<String> {
+    let key = sort
+        .iter()
+        .map(|sort_key| {
+            B::decode_value(row, &sort_key.field, field_type_of(field_types, &sort_key.field))
+                .map(|value| value.as_ref().map(FilterValue::from))
+        })
+        .collect::<RepositoryResult<Vec<_>>>()?;
+
+    let cursor = Cursor {
+        table: table.to_string(),
+        sort: sort.to_vec(),
+        key,
+        before,
+    };
+    Ok(cursor.encode())
+}
@@ -8,6 +8,15 @@ use thiserror::Error;
 /// Result type alias for repository operations
 pub type RepositoryResult<T> = Result<T, RepositoryError>;
 
+/// SQLSTATE reported when a serializable transaction cannot be serialized
+pub const SERIALIZATION_FAILURE: &str = "40001";
+
+/// SQLSTATE reported by PostgreSQL when a deadlock is detected
+pub const DEADLOCK_DETECTED: &str = "40P01";
+
+/// SQLSTATE reported by PostgreSQL when a function or operator does not exist
+pub const UNDEFINED_FUNCTION: &str = "42883";
+
 /// Error types that can occur during repository operations
 #[derive(Error, Debug)]
 pub enum RepositoryError {
@@ -46,6 +55,15 @@ pub enum RepositoryError {
         /// The backend that doesn't support it
         backend: String,
     },
+
+    /// Transaction kept failing with a retryable error until the attempt limit
+    #[error("Transaction failed after {attempts} attempts: {source}")]
+    RetriesExhausted {
+        /// Number of attempts made
+        attempts: u32,
+        /// Error from the last attempt
+        source: Box<RepositoryError>,
+    },
 }
 
 impl RepositoryError {
@@ -80,4 +98,23 @@ impl RepositoryError {
             backend: backend.to_string(),
         }
     }
+
+    /// SQLSTATE code of the underlying database error, if any
+    pub fn sqlstate(&self) -> Option<String> {
+        match self {
+            Self::Database(sqlx::Error::Database(db_err)) => db_err.code().map(|code| code.into_owned()),
+            _ => None,
+        }
+    }
+
+    /// Whether rerunning the transaction may succeed
+    ///
+    /// True for serialization failures (`40001`, also used by MySQL for
+    /// deadlocks) and PostgreSQL deadlocks (`40P01`).
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.sqlstate().as_deref(),
+            Some(SERIALIZATION_FAILURE) | Some(DEADLOCK_DETECTED)
+        )
+    }
 }
\ No newline at end of file
@@ -0,0 +1,649 @@
+//! Typed filter conditions for search queries
+//!
+//! A [`Filter`] compares one column against a value using a [`FilterOp`].
+//! Filters deserialize from JSON objects naming the field, the operator and
+//! its value, so list endpoints can accept them directly:
+//!
+//! ```json
+//! [
+//!     {"field": "created_at", "op": "gt", "value": "2024-01-01T00:00:00Z"},
+//!     {"field": "status", "op": "in", "value": ["active", "pending"]},
+//!     {"field": "deleted_by", "op": "is_null"}
+//! ]
+//! ```
+//!
+//! A [`FilterExpr`] combines filters into nested `and` / `or` / `not` groups:
+//!
+//! ```json
+//! {"and": [
+//!     {"or": [
+//!         {"field": "status", "op": "eq", "value": "active"},
+//!         {"field": "status", "op": "eq", "value": "pending"}
+//!     ]},
+//!     {"not": {"field": "department", "op": "eq", "value": "sales"}}
+//! ]}
+//! ```
+//!
+//! Values are always bound as query parameters, never interpolated into SQL.
+//! Before binding, each value is parsed as the [`FieldType`] the derive macro
+//! recorded for its column, so a `bool` column is compared with a boolean and
+//! a `DateTime<Utc>` column with a timestamp.
+//!
+//! Date and time columns also accept times relative to the current one, such
+//! as `now`, `now-7d` or `now+2h` (units `s`, `m`, `h`, `d` and `w`).
+//!
+//! In the `filters` map, a filterable numeric or date/time field also takes
+//! range bounds under `{field}_from` / `{field}_gte` and `{field}_to` /
+//! `{field}_lte`, both inclusive: `created_at_from=now-7d` keeps the last week.
+
+use crate::backends::{escape_like, DatabaseBackend, LIKE_ESCAPE};
+use crate::error::{RepositoryError, RepositoryResult};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+use std::fmt;
+
+/// A value compared against a column by a [`Filter`]
+///
+/// Deserializes from any JSON scalar. Timestamps, dates and UUIDs are given
+/// as text.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum FilterValue {
+    /// Boolean value
+    Bool(bool),
+    /// Integer value
+    Int(i64),
+    /// Floating point value
+    Float(f64),
+    /// Text value
+    Text(String),
+}
+
+impl fmt::Display for FilterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterValue::Bool(value) => write!(f, "{}", value),
+            FilterValue::Int(value) => write!(f, "{}", value),
+            FilterValue::Float(value) => write!(f, "{}", value),
+            FilterValue::Text(value) => f.write_str(value),
+        }
+    }
+}
+
+impl From<bool> for FilterValue {
+    fn from(value: bool) -> Self {
+        FilterValue::Bool(value)
+    }
+}
+
+impl From<i32> for FilterValue {
+    fn from(value: i32) -> Self {
+        FilterValue::Int(value.into())
+    }
+}
+
+impl From<i64> for FilterValue {
+    fn from(value: i64) -> Self {
+        FilterValue::Int(value)
+    }
+}
+
+impl From<f64> for FilterValue {
+    fn from(value: f64) -> Self {
+        FilterValue::Float(value)
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(value: &str) -> Self {
+        FilterValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(value: String) -> Self {
+        FilterValue::Text(value)
+    }
+}
+
+/// Column type a filter value is parsed as before it is bound
+///
+/// Recorded by `#[derive(Repository)]` for every entity field from its Rust
+/// type (`Option<T>` uses the type of `T`). Fields of other types are
+/// compared as [`Text`](FieldType::Text).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    /// `bool`
+    Bool,
+    /// `i16`
+    I16,
+    /// `i32` (and `u16`)
+    I32,
+    /// `i64` (and `u32`, `u64`)
+    I64,
+    /// `f32`
+    F32,
+    /// `f64`
+    F64,
+    /// `String` and any other type
+    Text,
+    /// `Uuid`
+    Uuid,
+    /// `DateTime<Utc>`
+    DateTime,
+    /// `NaiveDateTime`
+    NaiveDateTime,
+    /// `NaiveDate`
+    NaiveDate,
+    /// `NaiveTime`
+    NaiveTime,
+}
+
+impl FieldType {
+    /// Parse `value` as this type for the column `field`
+    ///
+    /// Text values are parsed with the type's usual string form: `true`/`false`
+    /// (or `1`/`0`) for booleans, RFC 3339 or a plain `YYYY-MM-DD` date for
+    /// `DateTime<Utc>`. Date and time types also take `now` with an optional
+    /// offset such as `now-7d`. Returns [`RepositoryError::Validation`] when
+    /// the value does not fit.
+    pub fn parse(self, field: &str, value: &FilterValue) -> RepositoryResult<SqlValue> {
+        let parsed = match (self, value) {
+            (FieldType::Text, value) => Some(SqlValue::Text(value.to_string())),
+            (FieldType::Bool, FilterValue::Bool(value)) => Some(SqlValue::Bool(*value)),
+            (FieldType::Bool, FilterValue::Text(text)) => match text.to_ascii_lowercase().as_str() {
+                "true" | "1" => Some(SqlValue::Bool(true)),
+                "false" | "0" => Some(SqlValue::Bool(false)),
+                _ => None,
+            },
+            (FieldType::I16, value) => parse_int(value).and_then(|v| i16::try_from(v).ok()).map(SqlValue::I16),
+            (FieldType::I32, value) => parse_int(value).and_then(|v| i32::try_from(v).ok()).map(SqlValue::I32),
+            (FieldType::I64, value) => parse_int(value).map(SqlValue::I64),
+            (FieldType::F32, value) => parse_float(value).map(|v| SqlValue::F32(v as f32)),
+            (FieldType::F64, value) => parse_float(value).map(SqlValue::F64),
+            (FieldType::Uuid, FilterValue::Text(text)) => Uuid::parse_str(text).ok().map(SqlValue::Uuid),
+            (FieldType::DateTime, FilterValue::Text(text)) if text.starts_with("now") => {
+                relative_time(text).map(SqlValue::DateTime)
+            }
+            (FieldType::NaiveDateTime, FilterValue::Text(text)) if text.starts_with("now") => {
+                relative_time(text).map(|time| SqlValue::NaiveDateTime(time.naive_utc()))
+            }
+            (FieldType::NaiveDate, FilterValue::Text(text)) if text.starts_with("now") => {
+                relative_time(text).map(|time| SqlValue::NaiveDate(time.date_naive()))
+            }
+            (FieldType::DateTime, FilterValue::Text(text)) => DateTime::parse_from_rfc3339(text)
+                .map(|value| value.with_timezone(&Utc))
+                .ok()
+                .or_else(|| {
+                    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                        .ok()
+                        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+                })
+                .map(SqlValue::DateTime),
+            (FieldType::NaiveDateTime, FilterValue::Text(text)) => text
+                .parse::<NaiveDateTime>()
+                .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f"))
+                .ok()
+                .map(SqlValue::NaiveDateTime),
+            (FieldType::NaiveDate, FilterValue::Text(text)) => text.parse::<NaiveDate>().ok().map(SqlValue::NaiveDate),
+            (FieldType::NaiveTime, FilterValue::Text(text)) => text.parse::<NaiveTime>().ok().map(SqlValue::NaiveTime),
+            _ => None,
+        };
+
+        parsed.ok_or_else(|| {
+            RepositoryError::validation(format!(
+                "Invalid value '{}' for field '{}': expected {}",
+                value,
+                field,
+                self.description()
+            ))
+        })
+    }
+
+    /// Whether the type is an integer or floating point number
+    pub fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            FieldType::I16 | FieldType::I32 | FieldType::I64 | FieldType::F32 | FieldType::F64
+        )
+    }
+
+    /// Whether the type is a date, time or timestamp
+    pub fn is_temporal(self) -> bool {
+        matches!(
+            self,
+            FieldType::DateTime | FieldType::NaiveDateTime | FieldType::NaiveDate | FieldType::NaiveTime
+        )
+    }
+
+    /// Human readable name used in validation errors
+    fn description(self) -> &'static str {
+        match self {
+            FieldType::Bool => "a boolean",
+            FieldType::I16 | FieldType::I32 | FieldType::I64 => "an integer",
+            FieldType::F32 | FieldType::F64 => "a number",
+            FieldType::Text => "text",
+            FieldType::Uuid => "a UUID",
+            FieldType::DateTime => "an RFC 3339 timestamp",
+            FieldType::NaiveDateTime => "a date and time",
+            FieldType::NaiveDate => "a date (YYYY-MM-DD)",
+            FieldType::NaiveTime => "a time (HH:MM:SS)",
+        }
+    }
+}
+
+/// Parse `now`, `now-7d` or `now+2h` as an offset from the current time
+fn relative_time(text: &str) -> Option<DateTime<Utc>> {
+    let offset = text.strip_prefix("now")?;
+    if offset.is_empty() {
+        return Some(Utc::now());
+    }
+    let (sign, amount) = if let Some(amount) = offset.strip_prefix('+') {
+        (1, amount)
+    } else {
+        (-1, offset.strip_prefix('-')?)
+    };
+    let unit = amount.chars().last()?;
+    let amount: i64 = amount[..amount.len() - unit.len_utf8()].parse().ok()?;
+    let delta = match unit {
+        's' => TimeDelta::try_seconds(amount),
+        'm' => TimeDelta::try_minutes(amount),
+        'h' => TimeDelta::try_hours(amount),
+        'd' => TimeDelta::try_days(amount),
+        'w' => TimeDelta::try_weeks(amount),
+        _ => None,
+    }?;
+    Utc::now().checked_add_signed(delta * sign)
+}
+
+fn parse_int(value: &FilterValue) -> Option<i64> {
+    match value {
+        FilterValue::Int(value) => Some(*value),
+        FilterValue::Text(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn parse_float(value: &FilterValue) -> Option<f64> {
+    match value {
+        FilterValue::Int(value) => Some(*value as f64),
+        FilterValue::Float(value) => Some(*value),
+        FilterValue::Text(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// A filter value parsed as its column's type, ready to bind
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    /// Boolean parameter
+    Bool(bool),
+    /// `SMALLINT` parameter
+    I16(i16),
+    /// `INTEGER` parameter
+    I32(i32),
+    /// `BIGINT` parameter
+    I64(i64),
+    /// `REAL` parameter
+    F32(f32),
+    /// `DOUBLE PRECISION` parameter
+    F64(f64),
+    /// Text parameter
+    Text(String),
+    /// UUID parameter
+    Uuid(Uuid),
+    /// Timestamp with time zone parameter
+    DateTime(DateTime<Utc>),
+    /// Timestamp without time zone parameter
+    NaiveDateTime(NaiveDateTime),
+    /// Date parameter
+    NaiveDate(NaiveDate),
+    /// Time of day parameter
+    NaiveTime(NaiveTime),
+}
+
+impl From<&SqlValue> for FilterValue {
+    /// The value in the form [`FieldType::parse`] reads back
+    fn from(value: &SqlValue) -> Self {
+        match value {
+            SqlValue::Bool(value) => FilterValue::Bool(*value),
+            SqlValue::I16(value) => FilterValue::Int((*value).into()),
+            SqlValue::I32(value) => FilterValue::Int((*value).into()),
+            SqlValue::I64(value) => FilterValue::Int(*value),
+            SqlValue::F32(value) => FilterValue::Float((*value).into()),
+            SqlValue::F64(value) => FilterValue::Float(*value),
+            SqlValue::Text(value) => FilterValue::Text(value.clone()),
+            SqlValue::Uuid(value) => FilterValue::Text(value.to_string()),
+            SqlValue::DateTime(value) => {
+                FilterValue::Text(value.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+            }
+            SqlValue::NaiveDateTime(value) => FilterValue::Text(value.format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+            SqlValue::NaiveDate(value) => FilterValue::Text(value.to_string()),
+            SqlValue::NaiveTime(value) => FilterValue::Text(value.format("%H:%M:%S%.f").to_string()),
+        }
+    }
+}
+
+/// Comparison applied by a [`Filter`]
+///
+/// Serialized as `"op"` with the operand, if any, under `"value"`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "op", content = "value", rename_all = "snake_case")]
+pub enum FilterOp {
+    /// `field = value`
+    Eq(FilterValue),
+    /// `field <> value`
+    Ne(FilterValue),
+    /// `field > value`
+    Gt(FilterValue),
+    /// `field >= value`
+    Gte(FilterValue),
+    /// `field < value`
+    Lt(FilterValue),
+    /// `field <= value`
+    Lte(FilterValue),
+    /// `field IN (values)`; an empty list matches nothing
+    In(Vec<FilterValue>),
+    /// `field NOT IN (values)`; an empty list matches everything
+    NotIn(Vec<FilterValue>),
+    /// `field IS NULL`
+    IsNull,
+    /// `field IS NOT NULL`
+    IsNotNull,
+    /// `field BETWEEN low AND high`, inclusive on both ends
+    Between(FilterValue, FilterValue),
+    /// Text starting with the given prefix
+    StartsWith(String),
+    /// Text containing the given substring
+    Contains(String),
+}
+
+/// A condition on a single field
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Filter {
+    /// Field (column) the condition applies to
+    pub field: String,
+    /// Comparison and operand
+    #[serde(flatten)]
+    pub op: FilterOp,
+}
+
+impl Filter {
+    /// Create a filter applying `op` to `field`
+    pub fn new(field: impl Into<String>, op: FilterOp) -> Self {
+        Self {
+            field: field.into(),
+            op,
+        }
+    }
+
+    /// `field = value`
+    pub fn eq(field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        Self::new(field, FilterOp::Eq(value.into()))
+    }
+
+    /// `field <> value`
+    pub fn ne(field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        Self::new(field, FilterOp::Ne(value.into()))
+    }
+
+    /// `field > value`
+    pub fn gt(field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        Self::new(field, FilterOp::Gt(value.into()))
+    }
+
+    /// `field >= value`
+    pub fn gte(field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        Self::new(field, FilterOp::Gte(value.into()))
+    }
+
+    /// `field < value`
+    pub fn lt(field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        Self::new(field, FilterOp::Lt(value.into()))
+    }
+
+    /// `field <= value`
+    pub fn lte(field: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        Self::new(field, FilterOp::Lte(value.into()))
+    }
+
+    /// `field IN (values)`
+    pub fn is_in<V: Into<FilterValue>>(field: impl Into<String>, values: impl IntoIterator<Item = V>) -> Self {
+        Self::new(field, FilterOp::In(values.into_iter().map(Into::into).collect()))
+    }
+
+    /// `field NOT IN (values)`
+    pub fn not_in<V: Into<FilterValue>>(field: impl Into<String>, values: impl IntoIterator<Item = V>) -> Self {
+        Self::new(field, FilterOp::NotIn(values.into_iter().map(Into::into).collect()))
+    }
+
+    /// `field IS NULL`
+    pub fn is_null(field: impl Into<String>) -> Self {
+        Self::new(field, FilterOp::IsNull)
+    }
+
+    /// `field IS NOT NULL`
+    pub fn is_not_null(field: impl Into<String>) -> Self {
+        Self::new(field, FilterOp::IsNotNull)
+    }
+
+    /// `field BETWEEN low AND high`
+    pub fn between(field: impl Into<String>, low: impl Into<FilterValue>, high: impl Into<FilterValue>) -> Self {
+        Self::new(field, FilterOp::Between(low.into(), high.into()))
+    }
+
+    /// Text field starting with `prefix`
+    pub fn starts_with(field: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self::new(field, FilterOp::StartsWith(prefix.into()))
+    }
+
+    /// Text field containing `needle`
+    pub fn contains(field: impl Into<String>, needle: impl Into<String>) -> Self {
+        Self::new(field, FilterOp::Contains(needle.into()))
+    }
+
+    /// Render the condition, pushing its operands onto `bind_values`
+    ///
+    /// Operands are parsed as `field_type`; pattern operators always bind
+    /// text. Placeholders are numbered after the values already in
+    /// `bind_values`.
+    pub(crate) fn to_sql<B: DatabaseBackend>(
+        &self,
+        field_type: FieldType,
+        bind_values: &mut Vec<SqlValue>,
+    ) -> RepositoryResult<String> {
+        let column = B::quote_identifier(&self.field);
+        let mut bind = |value: SqlValue| {
+            bind_values.push(value);
+            B::placeholder(bind_values.len())
+        };
+        let parse = |value: &FilterValue| field_type.parse(&self.field, value);
+
+        let sql = match &self.op {
+            FilterOp::Eq(value) => format!("{} = {}", column, bind(parse(value)?)),
+            FilterOp::Ne(value) => format!("{} <> {}", column, bind(parse(value)?)),
+            FilterOp::Gt(value) => format!("{} > {}", column, bind(parse(value)?)),
+            FilterOp::Gte(value) => format!("{} >= {}", column, bind(parse(value)?)),
+            FilterOp::Lt(value) => format!("{} < {}", column, bind(parse(value)?)),
+            FilterOp::Lte(value) => format!("{} <= {}", column, bind(parse(value)?)),
+            // `IN ()` is not valid SQL, so empty lists become constant conditions
+            FilterOp::In(values) if values.is_empty() => "1 = 0".to_string(),
+            FilterOp::NotIn(values) if values.is_empty() => "1 = 1".to_string(),
+            FilterOp::In(values) | FilterOp::NotIn(values) => {
+                let placeholders = values
+                    .iter()
+                    .map(|value| parse(value).map(&mut bind))
+                    .collect::<RepositoryResult<Vec<_>>>()?;
+                let operator = if matches!(self.op, FilterOp::In(_)) { "IN" } else { "NOT IN" };
+                format!("{} {} ({})", column, operator, placeholders.join(", "))
+            }
+            FilterOp::IsNull => format!("{} IS NULL", column),
+            FilterOp::IsNotNull => format!("{} IS NOT NULL", column),
+            FilterOp::Between(low, high) => {
+                let low = bind(parse(low)?);
+                let high = bind(parse(high)?);
+                format!("{} BETWEEN {} AND {}", column, low, high)
+            }
+            FilterOp::StartsWith(prefix) => {
+                let pattern = bind(SqlValue::Text(format!("{}%", escape_like(prefix))));
+                format!("{} LIKE {} ESCAPE '{}'", column, pattern, LIKE_ESCAPE)
+            }
+            FilterOp::Contains(needle) => {
+                let pattern = bind(SqlValue::Text(format!("%{}%", escape_like(needle))));
+                format!("{} LIKE {} ESCAPE '{}'", column, pattern, LIKE_ESCAPE)
+            }
+        };
+        Ok(sql)
+    }
+}
+
+/// A boolean combination of [`Filter`]s
+///
+/// Groups nest arbitrarily. An empty `and` matches every row and an empty
+/// `or` matches none. Plain filters deserialize as
+/// [`Condition`](FilterExpr::Condition), groups as `{"and": [...]}`,
+/// `{"or": [...]}` and `{"not": {...}}`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterExpr {
+    /// Every expression must match
+    And(Vec<FilterExpr>),
+    /// At least one expression must match
+    Or(Vec<FilterExpr>),
+    /// The expression must not match
+    Not(Box<FilterExpr>),
+    /// A single field condition
+    #[serde(untagged)]
+    Condition(Filter),
+}
+
+impl FilterExpr {
+    /// Match when every expression matches
+    pub fn and(exprs: impl IntoIterator<Item = impl Into<FilterExpr>>) -> Self {
+        FilterExpr::And(exprs.into_iter().map(Into::into).collect())
+    }
+
+    /// Match when at least one expression matches
+    pub fn or(exprs: impl IntoIterator<Item = impl Into<FilterExpr>>) -> Self {
+        FilterExpr::Or(exprs.into_iter().map(Into::into).collect())
+    }
+
+    /// Match when `expr` does not
+    pub fn not(expr: impl Into<FilterExpr>) -> Self {
+        FilterExpr::Not(Box::new(expr.into()))
+    }
+
+    /// Check that every condition targets one of `filterable_fields`
+    ///
+    /// Returns [`RepositoryError::Validation`] naming the first field that
+    /// may not be filtered on.
+    pub fn validate(&self, filterable_fields: &[&str]) -> RepositoryResult<()> {
+        match self {
+            FilterExpr::And(exprs) | FilterExpr::Or(exprs) => {
+                exprs.iter().try_for_each(|expr| expr.validate(filterable_fields))
+            }
+            FilterExpr::Not(expr) => expr.validate(filterable_fields),
+            FilterExpr::Condition(filter) if filterable_fields.contains(&filter.field.as_str()) => Ok(()),
+            FilterExpr::Condition(filter) => Err(RepositoryError::validation(format!(
+                "Field '{}' is not filterable",
+                filter.field
+            ))),
+        }
+    }
+
+    /// The expression with every condition on `field` lifted, or `None` once
+    /// nothing is left to match
+    ///
+    /// A lifted condition matches any value of `field`, so an `or` or `not`
+    /// containing one no longer restricts the rows and is dropped whole.
+    pub(crate) fn without_field(&self, field: &str) -> Option<FilterExpr> {
+        match self {
+            FilterExpr::Condition(filter) if filter.field == field => None,
+            FilterExpr::Condition(_) => Some(self.clone()),
+            FilterExpr::And(exprs) => {
+                let kept: Vec<FilterExpr> = exprs.iter().filter_map(|expr| expr.without_field(field)).collect();
+                (!kept.is_empty()).then_some(FilterExpr::And(kept))
+            }
+            FilterExpr::Or(exprs) => exprs
+                .iter()
+                .map(|expr| expr.without_field(field))
+                .collect::<Option<Vec<_>>>()
+                .map(FilterExpr::Or),
+            FilterExpr::Not(expr) => (expr.without_field(field).as_ref() == Some(&**expr)).then(|| self.clone()),
+        }
+    }
+
+    /// Render the expression, pushing operands onto `bind_values`
+    ///
+    /// Call [`validate`](Self::validate) first; fields are not checked here.
+    pub(crate) fn to_sql<B: DatabaseBackend>(
+        &self,
+        field_types: &[(&str, FieldType)],
+        bind_values: &mut Vec<SqlValue>,
+    ) -> RepositoryResult<String> {
+        let group = |exprs: &[FilterExpr], joiner: &str, bind_values: &mut Vec<SqlValue>| {
+            let parts = exprs
+                .iter()
+                .map(|expr| expr.to_sql::<B>(field_types, bind_values))
+                .collect::<RepositoryResult<Vec<_>>>()?;
+            Ok::<_, RepositoryError>(format!("({})", parts.join(joiner)))
+        };
+
+        match self {
+            FilterExpr::And(exprs) if exprs.is_empty() => Ok("1 = 1".to_string()),
+            FilterExpr::Or(exprs) if exprs.is_empty() => Ok("1 = 0".to_string()),
+            FilterExpr::And(exprs) => group(exprs, " AND ", bind_values),
+            FilterExpr::Or(exprs) => group(exprs, " OR ", bind_values),
+            FilterExpr::Not(expr) => Ok(format!("NOT ({})", expr.to_sql::<B>(field_types, bind_values)?)),
+            FilterExpr::Condition(filter) => {
+                filter.to_sql::<B>(field_type_of(field_types, &filter.field), bind_values)
+            }
+        }
+    }
+}
+
+impl From<Filter> for FilterExpr {
+    fn from(filter: Filter) -> Self {
+        FilterExpr::Condition(filter)
+    }
+}
+
+/// Look up the recorded type of `field`, defaulting to text
+pub(crate) fn field_type_of(field_types: &[(&str, FieldType)], field: &str) -> FieldType {
+    field_types
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map_or(FieldType::Text, |(_, field_type)| *field_type)
+}
+
+/// Comparison a range bound applies to its value
+pub(crate) type RangeBound = fn(FilterValue) -> FilterOp;
+
+/// Suffixes of `filters` keys bounding a range, with the comparison each applies
+const RANGE_SUFFIXES: [(&str, RangeBound); 4] = [
+    ("_from", FilterOp::Gte),
+    ("_gte", FilterOp::Gte),
+    ("_to", FilterOp::Lte),
+    ("_lte", FilterOp::Lte),
+];
+
+/// Split a `filters` key such as `created_at_from` into its field and bound
+///
+/// The field must be filterable and numeric or date/time; keys naming a
+/// filterable field outright are not ranges.
+pub(crate) fn range_bound<'a>(
+    key: &'a str,
+    filterable_fields: &[&str],
+    field_types: &[(&str, FieldType)],
+) -> Option<(&'a str, RangeBound)> {
+    if filterable_fields.contains(&key) {
+        return None;
+    }
+    RANGE_SUFFIXES.iter().find_map(|(suffix, bound)| {
+        let field = key.strip_suffix(suffix)?;
+        let field_type = field_type_of(field_types, field);
+        let is_range = field_type.is_numeric() || field_type.is_temporal();
+        (filterable_fields.contains(&field) && is_range).then_some((field, *bound))
+    })
+}
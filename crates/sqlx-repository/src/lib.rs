@@ -13,11 +13,14 @@
 //!
 //! - **Type-safe derive macro** - Automatic repository generation from structs
 //! - **Full CRUD operations** - Create, Read, Update, Delete with type safety  
-//! - **Search & pagination** - Flexible search with filtering and pagination
+//! - **Search & pagination** - Flexible search with typed filter operators and pagination
 //! - **Soft delete support** - Configurable soft delete with `deleted_at` field
+//! - **Transactions** - `*_in` variants and a `UnitOfWork` spanning several repositories
 //! - **Auto-generated types** - `CreateT` and `UpdateT` structs for mutations
 //! - **Multiple ID types** - Support for `i32`, `i64`, and `Uuid` primary keys
 //! - **PostgreSQL support** - Production-ready PostgreSQL backend
+//! - **SQLite support** - Same derived repositories on SQLite behind the `sqlite` feature
+//! - **MySQL support** - MySQL 8 / MariaDB behind the `mysql` feature
 //! - **Clear error messages** - Helpful compile-time errors with examples
 //!
 //! ## Quick Start
@@ -189,11 +192,139 @@
 //! # }
 //! ```
 //!
+//! ## Text Search
+//!
+//! [`SearchParams::query`] matches the `searchable_fields` as selected by
+//! [`SearchParams::match_mode`]: a case-insensitive substring by default, or a
+//! prefix, suffix, exact or case-sensitive match (see [`MatchMode`]). `LIKE`
+//! wildcards in the query match literally.
+//!
+//! On PostgreSQL, `#[repository(full_text(config = "english", fields(title, content)))]`
+//! switches text queries to `to_tsvector` / `websearch_to_tsquery`, with
+//! [`SearchParams::order_by_rank`] ordering by `ts_rank` and
+//! [`SearchParams::headlines`] filling [`SearchResult::headlines`] from `ts_headline`.
+//!
+//! [`MatchMode::Fuzzy`] tolerates typos using the `pg_trgm` extension, keeping
+//! matches above [`SearchParams::similarity_threshold`]; `order_by_rank` then
+//! orders by similarity. [`Repository::check_fuzzy_search`] reports a missing
+//! extension as [`RepositoryError::UnsupportedFeature`].
+//!
+//! ## Filtering
+//!
+//! Besides equality `filters`, [`SearchParams::conditions`] takes typed [`Filter`]s
+//! (`eq`, `ne`, `gt`, `gte`, `lt`, `lte`, `in`, `not_in`, `is_null`, `is_not_null`,
+//! `between`, `starts_with`, `contains`) on any of the `filterable_fields`:
+//!
+//! ```rust,ignore
+//! let results = repo.search(SearchParams {
+//!     conditions: vec![
+//!         Filter::gte("age", 18),
+//!         Filter::is_in("status", ["active", "pending"]),
+//!     ],
+//!     ..Default::default()
+//! }).await?;
+//! ```
+//!
+//! Filters deserialize from JSON such as `{"field": "status", "op": "in", "value": ["active"]}`.
+//! Values are parsed as the field's Rust type (see [`FieldType`]) and bound with
+//! that type; input that doesn't parse returns [`RepositoryError::Validation`].
+//!
+//! Numeric and date/time `filters` also take inclusive range bounds under
+//! `{field}_from` / `{field}_to` (or `_gte` / `_lte`), and date/time values
+//! may be relative, such as `created_at_from=now-7d`.
+//!
+//! [`SearchParams::filter`] takes a [`FilterExpr`] tree of nested `and` / `or` / `not`
+//! groups, validated against the `filterable_fields`:
+//!
+//! ```rust,ignore
+//! // (status = active OR status = pending) AND NOT department = sales
+//! let filter = FilterExpr::and([
+//!     FilterExpr::or([Filter::eq("status", "active"), Filter::eq("status", "pending")]),
+//!     FilterExpr::not(Filter::eq("department", "sales")),
+//! ]);
+//! ```
+//!
+//! Filters on fields that aren't `filterable_fields`, and the `deleted` scope
+//! without soft delete, return [`RepositoryError::Validation`] listing the
+//! unknown and allowed fields; `#[repository(strict = false)]` ignores them instead.
+//!
+//! [`SearchParams::facets`] adds per-value match counts of filterable fields to
+//! [`SearchResult::facets`]; [`Repository::facets`] returns them on their own.
+//! [`Repository::distinct_values`] lists a field's values with their counts, and
+//! [`Repository::suggest`] completes a prefix for typeahead.
+//!
+//! ## Sorting
+//!
+//! [`SearchParams::sort_by`] must be an entity field, and one of the
+//! `#[repository(sortable_fields(...))]` when that attribute is given; other
+//! values return [`RepositoryError::Validation`].
+//!
+//! [`SearchParams::sort`] sorts by several [`SortKey`]s, each with a direction
+//! and `NULL` placement, and parses from a compact form such as
+//! `-created_at:nulls_last,name`. The id is always the final tie-breaker.
+//!
+//! ## Query Strings
+//!
+//! [`SearchParams::from_query_string`] parses URLs such as
+//! `?filter[status]=active&filter[age][gte]=30&sort=-name&page[size]=20`, and
+//! [`SearchParams::to_query_string`] writes them; see the [`query_string`] module.
+//!
+//! [`SearchParams::from_search`] parses search-box input such as
+//! `status:active created_at>2026-01-01 -status:banned "john doe"` into free
+//! text and conditions; see the [`query_language`] module.
+//!
+//! ## Aggregates
+//!
+//! [`Repository::aggregate`] computes `COUNT`, `SUM`, `AVG`, `MIN` and `MAX` over
+//! the matches of a [`SearchParams`], optionally grouped; see the [`aggregate`] module.
+//!
+//! ## Cursor Pagination
+//!
+//! [`SearchResult::next_cursor`] and [`SearchResult::prev_cursor`] are opaque,
+//! signed cursors. Set one as [`SearchParams::cursor`] to fetch the adjacent page
+//! by keyset instead of by offset; see the [`cursor`] module for how they are
+//! signed and [`set_cursor_secret`] to share them between processes.
+//!
+//! [`SearchParams::count_mode`] skips or estimates [`SearchResult::total_count`]
+//! on large tables (see [`CountMode`]); [`SearchResult::has_next_page`] stays exact.
+//!
+//! Page sizes are checked against `#[repository(max_per_page = 100, default_per_page = 25)]`
+//! (1000 and 10 by default), with `per_page: 0` taking the default; see
+//! [`SearchParams::normalize`] and [`SearchParams::validate`].
+//!
+//! ## Transactions
+//!
+//! Each operation has a `*_in` variant taking `&mut` connection, which may be an
+//! open [`sqlx::Transaction`], so multiple repositories can commit or roll back together:
+//!
+//! ```rust,ignore
+//! let mut tx = pool.begin().await?;
+//! let user = user_repo.create_in(&mut tx, create_user).await?;
+//! post_repo.create_in(&mut tx, CreatePost { user_id: user.id, ..create_post }).await?;
+//! tx.commit().await?;
+//! ```
+//!
+//! [`UnitOfWork`] manages the transaction for you and hands out bound views of
+//! any derived repository, with nested savepoints via [`UnitOfWork::savepoint`]:
+//!
+//! ```rust,ignore
+//! let user = UnitOfWork::run(&pool, |uow| Box::pin(async move {
+//!     let user = uow.repo::<UserRepository>().create(create_user).await?;
+//!     uow.repo::<PostRepository>().create(CreatePost { user_id: user.id, ..create_post }).await?;
+//!     Ok(user)
+//! })).await?; // committed on Ok, rolled back on Err
+//! ```
+//!
+//! [`UnitOfWork::run_with_retry`] reruns the transaction on serialization failures
+//! and deadlocks according to a [`RetryPolicy`].
+//!
 //! ## UUID Primary Keys
 //!
-//! Use UUID primary keys instead of auto-incrementing integers for globally unique identifiers:
+//! Use UUID primary keys instead of auto-incrementing integers for globally unique identifiers
+//! (requires the `uuid` feature):
 //!
-//! ```rust
+#![cfg_attr(feature = "uuid", doc = "```rust")]
+#![cfg_attr(not(feature = "uuid"), doc = "```rust,ignore")]
 //! use chrono::{DateTime, Utc};
 //! use serde::{Deserialize, Serialize};
 //! use sqlx_repository::Repository;
@@ -212,7 +343,8 @@
 //!
 //! UUID primary keys are automatically generated during creation:
 //!
-//! ```rust,no_run
+#![cfg_attr(feature = "uuid", doc = "```rust,no_run")]
+#![cfg_attr(not(feature = "uuid"), doc = "```rust,ignore")]
 //! # use chrono::{DateTime, Utc};
 //! # use serde::{Deserialize, Serialize};
 //! # use sqlx_repository::Repository;
@@ -257,6 +389,45 @@
 //! uuid = { version = "1.0", features = ["v4"] }
 //! ```
 //!
+//! ## SQLite Support
+//!
+//! With the `sqlite` feature enabled, derived repositories can run against SQLite.
+//! When PostgreSQL is also enabled, select SQLite per repository with the `backend` attribute:
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+//! #[repository(table = "users")]
+//! #[repository(backend = "sqlite")]
+//! pub struct User {
+//!     pub id: i32,
+//!     pub name: String,
+//!     pub created_at: DateTime<Utc>,
+//!     pub updated_at: DateTime<Utc>,
+//! }
+//!
+//! let repo = UserRepository::new(sqlx::SqlitePool::connect("sqlite::memory:").await?);
+//! ```
+//!
+//! With only the `sqlite` feature enabled, SQLite is the default backend and no attribute is needed.
+//!
+//! ## MySQL Support
+//!
+//! The `mysql` feature adds a MySQL/MariaDB backend, selected with
+//! `#[repository(backend = "mysql")]` (or by default when PostgreSQL is disabled).
+//! MySQL has no `RETURNING` clause, so `create`, `update` and `restore` read the
+//! written row back by id, using `LAST_INSERT_ID()` for auto-increment keys.
+//! Estimated totals read `EXPLAIN FORMAT=TREE`, so they need MySQL 8.0.16 or
+//! later and are unavailable on MariaDB.
+//!
+//! ## Database Backends
+//!
+//! `Repository<T, ID, B>` is generic over a [`backends::DatabaseBackend`], which
+//! describes the SQL dialect (placeholders, identifier quoting, `RETURNING`
+//! support, the current timestamp, case-insensitive matching and type mapping)
+//! and builds every statement the default methods and the derive macro execute.
+//! Implement it for another sqlx driver and select it with
+//! `#[repository(backend = "my_crate::MyBackend")]` to plug in a new database.
+//!
 //! ## Supported Types
 //!
 //! | Category | Types | Example |
@@ -299,7 +470,7 @@
 //! ## Requirements
 //!
 //! - **Rust**: 1.70+
-//! - **Database**: PostgreSQL 12+ (MySQL and SQLite coming in future versions)
+//! - **Database**: PostgreSQL 12+, MySQL 8+ / MariaDB 10.5+ or SQLite 3.35+
 //! - **Required derives**: `Debug`, `Clone`, `Serialize`, `Deserialize`, `sqlx::FromRow`, `Repository`
 //! - **Primary key field**: `id` with supported types: `i32`, `i64`, or `Uuid`
 //!
@@ -327,7 +498,7 @@
 //! - **Search and pagination**: Built-in support for filtering, sorting, and pagination
 //! - **Soft deletes**: Optional soft delete functionality with restore capabilities
 //! - **Async/await**: Full async support built on tokio and sqlx
-//! - **Multi-database support**: PostgreSQL support now, MySQL and SQLite coming soon
+//! - **Multi-database support**: PostgreSQL, MySQL and SQLite behind Cargo features
 //!
 //! ## Repository Pattern
 //!
@@ -348,9 +519,14 @@
 #![warn(missing_docs, rust_2018_idioms)]
 
 // Re-export key types for convenient usage
+pub use aggregate::{Aggregate, AggregateFunc, AggregateQuery, AggregateRow};
+pub use cursor::set_cursor_secret;
 pub use error::{RepositoryError, RepositoryResult};
+pub use filter::{FieldType, Filter, FilterExpr, FilterOp, FilterValue, SqlValue};
 pub use repository::Repository;
-pub use search::{RecordScope, SearchParams, SearchResult, SortOrder};
+pub use retry::RetryPolicy;
+pub use search::{CountMode, FacetCount, FullTextConfig, MatchMode, NullsOrder, RecordScope, SearchParams, SearchResult, SortKey, SortOrder, ValueOrder};
+pub use unit_of_work::{TransactionalRepository, TxRepository, UnitOfWork};
 
 // Re-export derive macro when macros feature is enabled
 #[cfg(feature = "macros")]
@@ -358,9 +534,16 @@ pub use search::{RecordScope, SearchParams, SearchResult, SortOrder};
 pub use sqlx_repository_macros::Repository;
 
 // Core modules
+pub mod aggregate;
+pub mod cursor;
 pub mod error;
+pub mod filter;
+pub mod query_language;
+pub mod query_string;
 pub mod repository;
+pub mod retry;
 pub mod search;
+pub mod unit_of_work;
 
 /// ID generation trait for different primary key types
 pub trait IdGenerator<T> {
@@ -391,15 +574,19 @@ pub mod backends;
 /// 
 /// // Now you have access to:
 /// // - Repository trait
-/// // - SearchParams, SearchResult, SortOrder, RecordScope
+/// // - SearchParams, SearchResult, SortOrder, SortKey, MatchMode, RecordScope
 /// // - RepositoryError, RepositoryResult
+/// // - UnitOfWork, TransactionalRepository, RetryPolicy
 /// // - Repository derive macro (if macros feature is enabled)
 /// ```
+#[allow(clippy::mixed_attributes_style)]
 pub mod prelude {
     //! Common imports for sqlx-repository users
     
-    pub use crate::{Repository, SearchParams, SearchResult, SortOrder, RecordScope};
+    pub use crate::{Aggregate, AggregateQuery, CountMode, FacetCount, MatchMode, Repository, SearchParams, SearchResult, SortKey, SortOrder, RecordScope, ValueOrder};
+    pub use crate::{Filter, FilterExpr, FilterOp, FilterValue};
     pub use crate::{RepositoryError, RepositoryResult};
+    pub use crate::{RetryPolicy, TransactionalRepository, UnitOfWork};
     
     #[cfg(feature = "macros")]
     #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
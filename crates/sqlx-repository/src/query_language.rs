@@ -0,0 +1,223 @@
+//! Search-box query language
+//!
+//! [`SearchParams::from_search`] parses what people type into a search box:
+//!
+//! ```text
+//! status:active department:eng,sales created_at>2026-01-01 -status:banned "john doe"
+//! ```
+//!
+//! Terms are separated by whitespace, and all of them must match:
+//!
+//! | Term | Meaning |
+//! |------|---------|
+//! | `word`, `"some words"` | Free text, joined into [`SearchParams::query`] |
+//! | `field:value` | `field` equals `value` |
+//! | `field:a,b` | `field` is one of the listed values |
+//! | `field>value`, `field>=value`, `field<value`, `field<=value` | Comparison; `field:>value` and so on also work |
+//! | `-field:value`, `-field>value`, ... | The opposite condition |
+//!
+//! Field terms become [`SearchParams::conditions`], so they are checked
+//! against the `filterable_fields` and their values parsed as the field's type
+//! when the search runs. A value in double quotes may contain spaces and
+//! commas; `\"` and `\\` escape a quote or backslash inside quotes.
+//!
+//! Malformed input returns [`RepositoryError::Validation`] with the 1-based
+//! column of the problem, such as
+//! `Invalid search at column 8: unterminated quote`.
+
+use crate::error::{RepositoryError, RepositoryResult};
+use crate::filter::{Filter, FilterOp, FilterValue};
+use crate::search::SearchParams;
+
+impl SearchParams {
+    /// Parse a search-box query into params with its free text and conditions
+    ///
+    /// Every other field keeps its [`Default`] value. See the
+    /// [`query_language`](crate::query_language) module for the syntax.
+    pub fn from_search(input: &str) -> RepositoryResult<Self> {
+        let mut parser = Parser {
+            chars: input.chars().collect(),
+            pos: 0,
+        };
+        let mut text = Vec::new();
+        let mut conditions = Vec::new();
+
+        while parser.skip_whitespace() {
+            match parser.term()? {
+                Term::Text(word) => text.push(word),
+                Term::Condition(filter) => conditions.push(filter),
+            }
+        }
+
+        Ok(SearchParams {
+            query: Some(text.join(" ")).filter(|query| !query.is_empty()),
+            conditions,
+            ..Default::default()
+        })
+    }
+}
+
+enum Term {
+    Text(String),
+    Condition(Filter),
+}
+
+#[derive(Clone, Copy)]
+enum Comparison {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    /// Skip whitespace, returning whether any input is left
+    fn skip_whitespace(&mut self) -> bool {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        self.pos < self.chars.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    // `Option::is_none_or` needs Rust 1.82, past the crate's 1.70 minimum
+    #[allow(clippy::unnecessary_map_or)]
+    fn at_term_end(&self) -> bool {
+        self.peek().map_or(true, char::is_whitespace)
+    }
+
+    fn error(&self, pos: usize, reason: &str) -> RepositoryError {
+        RepositoryError::validation(format!("Invalid search at column {}: {}", pos + 1, reason))
+    }
+
+    fn term(&mut self) -> RepositoryResult<Term> {
+        let start = self.pos;
+        let negated = self.peek() == Some('-') && self.chars.get(start + 1).is_some_and(|c| !c.is_whitespace());
+        if negated {
+            self.pos += 1;
+        }
+
+        if self.peek() == Some('"') {
+            let phrase = self.quoted()?;
+            if negated {
+                return Err(self.error(start, "free text cannot be negated"));
+            }
+            return Ok(Term::Text(phrase));
+        }
+
+        let field_start = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        let field: String = self.chars[field_start..self.pos].iter().collect();
+        let comparison = if field.is_empty() { None } else { self.comparison() };
+
+        let Some(comparison) = comparison else {
+            if negated {
+                return Err(self.error(start, "free text cannot be negated"));
+            }
+            self.pos = field_start;
+            return Ok(Term::Text(self.word()));
+        };
+
+        let value_start = self.pos;
+        let values = if self.peek() == Some('"') {
+            vec![self.quoted()?]
+        } else {
+            let word = self.word();
+            match comparison {
+                Comparison::Eq => word.split(',').filter(|v| !v.is_empty()).map(str::to_string).collect(),
+                _ => Some(word).filter(|v| !v.is_empty()).into_iter().collect(),
+            }
+        };
+        if values.is_empty() {
+            return Err(self.error(value_start, &format!("expected a value for '{}'", field)));
+        }
+
+        Ok(Term::Condition(Filter::new(field, condition(comparison, values, negated))))
+    }
+
+    /// Comparison operator after a field name, if there is one
+    fn comparison(&mut self) -> Option<Comparison> {
+        let colon = self.peek() == Some(':');
+        if colon {
+            self.pos += 1;
+        }
+        let comparison = match (self.peek(), self.chars.get(self.pos + 1).copied()) {
+            (Some('>'), Some('=')) => Some((Comparison::Gte, 2)),
+            (Some('<'), Some('=')) => Some((Comparison::Lte, 2)),
+            (Some('>'), _) => Some((Comparison::Gt, 1)),
+            (Some('<'), _) => Some((Comparison::Lt, 1)),
+            _ => None,
+        };
+        match comparison {
+            Some((comparison, len)) => {
+                self.pos += len;
+                Some(comparison)
+            }
+            None if colon => Some(Comparison::Eq),
+            None => None,
+        }
+    }
+
+    /// Characters up to the next whitespace
+    fn word(&mut self) -> String {
+        let start = self.pos;
+        while !self.at_term_end() {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// A double-quoted string, with `\"` and `\\` escapes
+    fn quoted(&mut self) -> RepositoryResult<String> {
+        let open = self.pos;
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error(open, "unterminated quote")),
+                Some('"') => break,
+                Some('\\') if matches!(self.chars.get(self.pos + 1), Some('"' | '\\')) => {
+                    text.push(self.chars[self.pos + 1]);
+                    self.pos += 2;
+                }
+                Some(c) => {
+                    text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+        self.pos += 1;
+        if !self.at_term_end() {
+            return Err(self.error(self.pos, "expected whitespace after closing quote"));
+        }
+        Ok(text)
+    }
+}
+
+/// Filter operator for a comparison, flipped when negated
+fn condition(comparison: Comparison, mut values: Vec<String>, negated: bool) -> FilterOp {
+    if values.len() > 1 {
+        let values = values.into_iter().map(FilterValue::from).collect();
+        return if negated { FilterOp::NotIn(values) } else { FilterOp::In(values) };
+    }
+    let value = FilterValue::from(values.remove(0));
+    match (comparison, negated) {
+        (Comparison::Eq, false) => FilterOp::Eq(value),
+        (Comparison::Eq, true) => FilterOp::Ne(value),
+        (Comparison::Gt, false) | (Comparison::Lte, true) => FilterOp::Gt(value),
+        (Comparison::Gte, false) | (Comparison::Lt, true) => FilterOp::Gte(value),
+        (Comparison::Lt, false) | (Comparison::Gte, true) => FilterOp::Lt(value),
+        (Comparison::Lte, false) | (Comparison::Gt, true) => FilterOp::Lte(value),
+    }
+}
@@ -0,0 +1,331 @@
+//! URL query-string form of [`SearchParams`]
+//!
+//! [`SearchParams::from_query_string`] parses list endpoint URLs such as
+//!
+//! ```text
+//! ?q=jane&filter[status]=active&filter[age][gte]=30&sort=-name&page[size]=20
+//! ```
+//!
+//! and [`SearchParams::to_query_string`] writes params back in the same form,
+//! for pagination links. Keys and values are percent-decoded first, so
+//! `filter%5Bstatus%5D` and `filter[status]` are the same parameter.
+//!
+//! | Parameter | Field |
+//! |-----------|-------|
+//! | `q` | [`query`](SearchParams::query) |
+//! | `match` | [`match_mode`](SearchParams::match_mode): `contains`, `prefix`, `suffix`, `exact`, `case_sensitive`, `fuzzy` |
+//! | `similarity` | [`similarity_threshold`](SearchParams::similarity_threshold) |
+//! | `rank`, `headlines` | [`order_by_rank`](SearchParams::order_by_rank), [`headlines`](SearchParams::headlines): `true` or `false` |
+//! | `filter[field]` | [`filters`](SearchParams::filters) entry: equality, or a range bound such as `filter[created_at_from]` |
+//! | `filter[field][op]` | [`conditions`](SearchParams::conditions) entry, `op` being a [`FilterOp`] name |
+//! | `expr` | [`filter`](SearchParams::filter) as JSON |
+//! | `facets` | [`facets`](SearchParams::facets), comma-separated |
+//! | `sort` | [`sort`](SearchParams::sort) in [`SortKey::parse_list`] form |
+//! | `page[number]`, `page[size]` | [`page`](SearchParams::page) (0-based), [`per_page`](SearchParams::per_page) |
+//! | `page[cursor]` | [`cursor`](SearchParams::cursor) |
+//! | `count` | [`count_mode`](SearchParams::count_mode): `exact`, `estimated`, `none` |
+//! | `values` | [`value_order`](SearchParams::value_order): `frequency`, `alphabetical` |
+//! | `scope` | [`scope`](SearchParams::scope): `active`, `deleted`, `all` |
+//!
+//! `in` and `not_in` take one value per repeated key
+//! (`filter[status][in]=active&filter[status][in]=pending`), a single empty
+//! value standing for an empty list; `between` takes exactly two.
+//! `is_null` and `is_not_null` take `true` or no value. Every other parameter
+//! may appear once. Unknown parameters, operators and malformed values return
+//! [`RepositoryError::Validation`] naming the parameter; use
+//! [`SearchParams::from_query_pairs`] to parse only some of a URL's parameters.
+
+use crate::error::{RepositoryError, RepositoryResult};
+use crate::filter::{Filter, FilterExpr, FilterOp, FilterValue};
+use crate::search::{SearchParams, SortKey, SortOrder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashSet;
+
+impl SearchParams {
+    /// Parse params from a URL query string, with or without the leading `?`
+    ///
+    /// Parameters that are not given keep their [`Default`] values. See the
+    /// [`query_string`](crate::query_string) module for the format.
+    pub fn from_query_string(query: &str) -> RepositoryResult<Self> {
+        let query = query.strip_prefix('?').unwrap_or(query);
+        Self::from_query_pairs(form_urlencoded::parse(query.as_bytes()))
+    }
+
+    /// Parse params from decoded key-value pairs, such as a web framework's query map
+    pub fn from_query_pairs<K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> RepositoryResult<Self>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut params = SearchParams::default();
+        let mut seen = HashSet::new();
+        // Conditions by field and operator, in the order first seen
+        let mut conditions: Vec<(String, String, String, Vec<String>)> = Vec::new();
+
+        for (key, value) in pairs {
+            let (key, value) = (key.as_ref(), value.as_ref());
+            let invalid = |reason: &str| invalid_parameter(key, reason);
+
+            if let Some(rest) = key.strip_prefix("filter[") {
+                let (field, op) = match rest.split_once(']') {
+                    Some((field, "")) => (field, None),
+                    Some((field, op)) => match op.strip_prefix('[').and_then(|op| op.strip_suffix(']')) {
+                        Some(op) => (field, Some(op)),
+                        None => return Err(invalid("expected filter[field] or filter[field][op]")),
+                    },
+                    None => return Err(invalid("expected filter[field] or filter[field][op]")),
+                };
+                if field.is_empty() {
+                    return Err(invalid("missing field name"));
+                }
+                match op {
+                    None => {
+                        if params.filters.insert(field.to_string(), value.to_string()).is_some() {
+                            return Err(invalid("given more than once"));
+                        }
+                    }
+                    Some(op) => match conditions.iter_mut().find(|(f, o, _, _)| f == field && o == op) {
+                        Some((_, _, _, values)) => values.push(value.to_string()),
+                        None => conditions.push((field.to_string(), op.to_string(), key.to_string(), vec![value.to_string()])),
+                    },
+                }
+                continue;
+            }
+
+            if !seen.insert(key.to_string()) {
+                return Err(invalid("given more than once"));
+            }
+            match key {
+                "q" => params.query = Some(value.to_string()).filter(|q| !q.is_empty()),
+                "match" => params.match_mode = parse_enum(key, value)?,
+                "similarity" => {
+                    params.similarity_threshold = Some(value.parse().map_err(|_| invalid(&format!("expected a number, got '{}'", value)))?)
+                }
+                "rank" => params.order_by_rank = parse_bool(key, value)?,
+                "headlines" => params.headlines = parse_bool(key, value)?,
+                "expr" => {
+                    let expr: FilterExpr = serde_json::from_str(value).map_err(|err| invalid(&err.to_string()))?;
+                    params.filter = Some(expr);
+                }
+                "facets" => params.facets = split_list(value),
+                "sort" => params.sort = SortKey::parse_list(value).map_err(|err| invalid(&err.to_string()))?,
+                "page[number]" => params.page = parse_u32(key, value)?,
+                "page[size]" => params.per_page = parse_u32(key, value)?,
+                "page[cursor]" => params.cursor = Some(value.to_string()).filter(|c| !c.is_empty()),
+                "count" => params.count_mode = parse_enum(key, value)?,
+                "values" => params.value_order = parse_enum(key, value)?,
+                "scope" => params.scope = parse_enum(key, value)?,
+                _ => return Err(invalid("unknown parameter")),
+            }
+        }
+
+        params.conditions = conditions
+            .into_iter()
+            .map(|(field, op, key, values)| parse_condition(field, &op, &key, values))
+            .collect::<RepositoryResult<_>>()?;
+        Ok(params)
+    }
+
+    /// Write params as a URL query string, without the leading `?`
+    ///
+    /// Only values that differ from the [`Default`] are written, and
+    /// `filters` are sorted by field so equal params give equal strings.
+    /// Parsing the result with [`from_query_string`](Self::from_query_string)
+    /// gives back the same search; `sort_by` and `sort_order` come back as `sort`.
+    pub fn to_query_string(&self) -> String {
+        let defaults = SearchParams::default();
+        let mut out = form_urlencoded::Serializer::new(String::new());
+
+        if let Some(query) = &self.query {
+            out.append_pair("q", query);
+        }
+        if self.match_mode != defaults.match_mode {
+            out.append_pair("match", &enum_str(&self.match_mode));
+        }
+        if let Some(threshold) = self.similarity_threshold {
+            out.append_pair("similarity", &threshold.to_string());
+        }
+        if self.order_by_rank {
+            out.append_pair("rank", "true");
+        }
+        if self.headlines {
+            out.append_pair("headlines", "true");
+        }
+
+        let mut filters: Vec<_> = self.filters.iter().collect();
+        filters.sort();
+        for (field, value) in filters {
+            out.append_pair(&format!("filter[{}]", field), value);
+        }
+        for condition in &self.conditions {
+            let key = |op: &str| format!("filter[{}][{}]", condition.field, op);
+            let op = op_name(&condition.op);
+            match &condition.op {
+                FilterOp::Eq(value)
+                | FilterOp::Ne(value)
+                | FilterOp::Gt(value)
+                | FilterOp::Gte(value)
+                | FilterOp::Lt(value)
+                | FilterOp::Lte(value) => {
+                    out.append_pair(&key(&op), &value.to_string());
+                }
+                FilterOp::In(values) | FilterOp::NotIn(values) => {
+                    if values.is_empty() {
+                        out.append_pair(&key(&op), "");
+                    }
+                    for value in values {
+                        out.append_pair(&key(&op), &value.to_string());
+                    }
+                }
+                FilterOp::IsNull | FilterOp::IsNotNull => {
+                    out.append_pair(&key(&op), "true");
+                }
+                FilterOp::Between(low, high) => {
+                    out.append_pair(&key(&op), &low.to_string());
+                    out.append_pair(&key(&op), &high.to_string());
+                }
+                FilterOp::StartsWith(text) | FilterOp::Contains(text) => {
+                    out.append_pair(&key(&op), text);
+                }
+            }
+        }
+        if let Some(expr) = &self.filter {
+            let json = serde_json::to_string(expr).expect("filter expressions serialize to JSON");
+            out.append_pair("expr", &json);
+        }
+        if !self.facets.is_empty() {
+            out.append_pair("facets", &self.facets.join(","));
+        }
+
+        let sort = if !self.sort.is_empty() {
+            self.sort.clone()
+        } else if let Some(field) = &self.sort_by {
+            vec![SortKey::new(field.as_str(), self.sort_order)]
+        } else if self.sort_order == SortOrder::Desc {
+            vec![SortKey::desc("id")]
+        } else {
+            Vec::new()
+        };
+        if !sort.is_empty() {
+            let list: Vec<String> = sort.iter().map(SortKey::to_string).collect();
+            out.append_pair("sort", &list.join(","));
+        }
+
+        if let Some(cursor) = &self.cursor {
+            out.append_pair("page[cursor]", cursor);
+        }
+        if self.page != defaults.page {
+            out.append_pair("page[number]", &self.page.to_string());
+        }
+        if self.per_page != defaults.per_page {
+            out.append_pair("page[size]", &self.per_page.to_string());
+        }
+        if self.count_mode != defaults.count_mode {
+            out.append_pair("count", &enum_str(&self.count_mode));
+        }
+        if self.value_order != defaults.value_order {
+            out.append_pair("values", &enum_str(&self.value_order));
+        }
+        let scope = enum_str(&self.scope);
+        if scope != enum_str(&defaults.scope) {
+            out.append_pair("scope", &scope);
+        }
+
+        out.finish()
+    }
+}
+
+/// Build the condition for all values given for `filter[field][op]`
+fn parse_condition(field: String, op: &str, key: &str, mut values: Vec<String>) -> RepositoryResult<Filter> {
+    let single = |values: &mut Vec<String>| match values.len() {
+        1 => Ok(values.remove(0)),
+        _ => Err(invalid_parameter(key, "given more than once")),
+    };
+    let flag = |values: &mut Vec<String>| match single(values)?.as_str() {
+        "" | "true" => Ok(()),
+        _ => Err(invalid_parameter(key, "expected 'true' or no value")),
+    };
+
+    let op = match op {
+        "eq" => FilterOp::Eq(single(&mut values)?.into()),
+        "ne" => FilterOp::Ne(single(&mut values)?.into()),
+        "gt" => FilterOp::Gt(single(&mut values)?.into()),
+        "gte" => FilterOp::Gte(single(&mut values)?.into()),
+        "lt" => FilterOp::Lt(single(&mut values)?.into()),
+        "lte" => FilterOp::Lte(single(&mut values)?.into()),
+        "in" => FilterOp::In(list_values(values)),
+        "not_in" => FilterOp::NotIn(list_values(values)),
+        "is_null" => flag(&mut values).map(|_| FilterOp::IsNull)?,
+        "is_not_null" => flag(&mut values).map(|_| FilterOp::IsNotNull)?,
+        "between" => match <[String; 2]>::try_from(values) {
+            Ok([low, high]) => FilterOp::Between(low.into(), high.into()),
+            Err(values) => {
+                return Err(invalid_parameter(
+                    key,
+                    &format!("expected 2 values, got {}", values.len()),
+                ))
+            }
+        },
+        "starts_with" => FilterOp::StartsWith(single(&mut values)?),
+        "contains" => FilterOp::Contains(single(&mut values)?),
+        _ => return Err(invalid_parameter(key, &format!("unknown filter operator '{}'", op))),
+    };
+    Ok(Filter::new(field, op))
+}
+
+/// Values of an `in` / `not_in` list, where a lone empty value is an empty list
+fn list_values(values: Vec<String>) -> Vec<FilterValue> {
+    if values.len() == 1 && values[0].is_empty() {
+        return Vec::new();
+    }
+    values.into_iter().map(FilterValue::from).collect()
+}
+
+/// Name of an operator as written in `filter[field][op]`
+fn op_name(op: &FilterOp) -> String {
+    let json = serde_json::to_value(op).expect("filter operators serialize to JSON");
+    json["op"].as_str().unwrap_or_default().to_string()
+}
+
+/// Comma-separated names, skipping empty entries
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_bool(key: &str, value: &str) -> RepositoryResult<bool> {
+    match value {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(invalid_parameter(key, &format!("expected 'true' or 'false', got '{}'", value))),
+    }
+}
+
+fn parse_u32(key: &str, value: &str) -> RepositoryResult<u32> {
+    value
+        .parse()
+        .map_err(|_| invalid_parameter(key, &format!("expected a non-negative integer, got '{}'", value)))
+}
+
+/// Parse a unit enum variant by its serialized name
+fn parse_enum<T: DeserializeOwned>(key: &str, value: &str) -> RepositoryResult<T> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| invalid_parameter(key, &format!("unknown value '{}'", value)))
+}
+
+/// Serialized name of a unit enum variant
+fn enum_str<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => unreachable!("unit enum variants serialize to strings"),
+    }
+}
+
+fn invalid_parameter(key: &str, reason: &str) -> RepositoryError {
+    RepositoryError::validation(format!("Invalid query parameter '{}': {}", key, reason))
+}
@@ -1,21 +1,36 @@
 //! Core repository trait and implementations
 
-use crate::error::{RepositoryError, RepositoryResult};
-use crate::search::{SearchParams, SearchResult, SortOrder, RecordScope};
+use crate::aggregate::{AggregateQuery, AggregateRow};
+use crate::backends::{plan_rows_estimate, DatabaseBackend, DefaultBackend};
+use crate::cursor::{cursor_for_row, Cursor};
+use crate::error::{RepositoryError, RepositoryResult, UNDEFINED_FUNCTION};
+use crate::filter::{field_type_of, range_bound, FieldType, Filter, FilterValue, SqlValue};
+use crate::search::{CountMode, FacetCount, FullTextConfig, MatchMode, SearchParams, SearchResult, RecordScope, SortKey, ValueOrder};
 use async_trait::async_trait;
-use sqlx::FromRow;
+use std::collections::{HashMap, HashSet};
+use sqlx::{Column, ColumnIndex, Database, Decode, Encode, Executor, FromRow, IntoArguments, Row, Type};
 
 /// Core repository trait providing CRUD operations and search functionality
+///
+/// The `B` parameter selects the [`DatabaseBackend`] whose SQL dialect the
+/// default methods speak. It defaults to [`DefaultBackend`], so existing
+/// `Repository<T, ID>` implementations keep targeting PostgreSQL.
 #[async_trait]
-pub trait Repository<T, ID = i32>: Send + Sync
+pub trait Repository<T, ID = i32, B = DefaultBackend>: Send + Sync
 where
-    T: for<'r> FromRow<'r, sqlx::postgres::PgRow> + Send + Unpin,
-    ID: Send + Sync + Clone + 'static + for<'e> sqlx::Encode<'e, sqlx::Postgres> + for<'d> sqlx::Decode<'d, sqlx::Postgres> + sqlx::Type<sqlx::Postgres>,
+    B: DatabaseBackend,
+    T: for<'r> FromRow<'r, <B::Database as Database>::Row> + Send + Unpin,
+    ID: Send + Sync + Clone + 'static + for<'e> Encode<'e, B::Database> + for<'d> Decode<'d, B::Database> + Type<B::Database>,
+    for<'q> <B::Database as Database>::Arguments<'q>: IntoArguments<'q, B::Database>,
+    for<'c> &'c mut <B::Database as Database>::Connection: Executor<'c, Database = B::Database>,
+    String: for<'e> Encode<'e, B::Database> + Type<B::Database>,
+    i64: for<'d> Decode<'d, B::Database> + Type<B::Database>,
+    usize: ColumnIndex<<B::Database as Database>::Row>,
 {
     /// Type for creating new entities
-    type CreateType: Send;
+    type CreateType: Send + 'static;
     /// Type for updating existing entities  
-    type UpdateType: Send;
+    type UpdateType: Send + 'static;
 
     // Required implementations (provided by derive macro)
     /// Get the database table name for this repository
@@ -32,151 +47,626 @@ where
     fn filterable_fields() -> &'static [&'static str] {
         &[]
     }
+    /// Get list of fields that searches can be sorted by
+    ///
+    /// When empty, any entity field may be used.
+    fn sortable_fields() -> &'static [&'static str] {
+        &[]
+    }
+    /// Get list of fields that aggregates may name
+    ///
+    /// When empty, any numeric or date/time entity field may be used.
+    fn aggregatable_fields() -> &'static [&'static str] {
+        &[]
+    }
+    /// Check if unknown filter fields and scopes are rejected rather than ignored
+    ///
+    /// On unless the entity has `#[repository(strict = false)]`. Sort fields
+    /// are checked either way.
+    fn strict_params() -> bool {
+        true
+    }
+    /// Get the page size used when `per_page` is 0
+    fn default_per_page() -> u32 {
+        10
+    }
+    /// Get the largest page size searches may request
+    ///
+    /// Larger `per_page` values are rejected when [`strict_params`](Self::strict_params)
+    /// is on and lowered to this otherwise.
+    fn max_per_page() -> u32 {
+        1000
+    }
+    /// Get the full-text search settings, if text queries use full-text search
+    fn full_text() -> Option<FullTextConfig> {
+        None
+    }
+    /// Get the column type of each entity field, used to parse and bind filter values
+    ///
+    /// Fields missing from the list are compared as text.
+    fn field_types() -> &'static [(&'static str, FieldType)] {
+        &[]
+    }
     /// Get the database connection pool
-    fn pool(&self) -> &sqlx::PgPool;
+    fn pool(&self) -> &sqlx::Pool<B::Database>;
 
     // Required method implementations (must be provided by implementor)
+    /// Create a new entity on the given connection
+    async fn create_in(&self, conn: &mut <B::Database as Database>::Connection, data: Self::CreateType) -> RepositoryResult<T>;
+    /// Update an existing entity by ID on the given connection
+    async fn update_in(&self, conn: &mut <B::Database as Database>::Connection, id: ID, data: Self::UpdateType) -> RepositoryResult<Option<T>>;
+
+    // Pool-backed operations, each running on a connection acquired from `pool()`
     /// Create a new entity
-    async fn create(&self, data: Self::CreateType) -> RepositoryResult<T>;
+    async fn create(&self, data: Self::CreateType) -> RepositoryResult<T> {
+        let mut conn = self.pool().acquire().await?;
+        self.create_in(&mut conn, data).await
+    }
+
     /// Update an existing entity by ID
-    async fn update(&self, id: ID, data: Self::UpdateType) -> RepositoryResult<Option<T>>;
+    async fn update(&self, id: ID, data: Self::UpdateType) -> RepositoryResult<Option<T>> {
+        let mut conn = self.pool().acquire().await?;
+        self.update_in(&mut conn, id, data).await
+    }
 
-    // Default implementations using PostgreSQL (will be abstracted in Phase 2)
     /// Find an entity by its ID
     async fn find_by_id(&self, id: ID) -> RepositoryResult<Option<T>> {
-        let query = if Self::soft_delete_enabled() {
-            format!("SELECT * FROM {} WHERE id = $1 AND deleted_at IS NULL", Self::table_name())
-        } else {
-            format!("SELECT * FROM {} WHERE id = $1", Self::table_name())
+        let mut conn = self.pool().acquire().await?;
+        self.find_by_id_in(&mut conn, id).await
+    }
+
+    /// Find all entities
+    async fn find_all(&self) -> RepositoryResult<Vec<T>> {
+        let mut conn = self.pool().acquire().await?;
+        self.find_all_in(&mut conn).await
+    }
+
+    /// Delete an entity by ID (soft delete if enabled, otherwise hard delete)
+    async fn delete(&self, id: ID) -> RepositoryResult<bool> {
+        let mut conn = self.pool().acquire().await?;
+        self.delete_in(&mut conn, id).await
+    }
+
+    /// Search entities with filtering, pagination, and sorting
+    async fn search(&self, params: SearchParams) -> RepositoryResult<SearchResult<T>> {
+        let mut conn = self.pool().acquire().await?;
+        self.search_in(&mut conn, params).await
+    }
+
+    /// Restore a soft-deleted entity by ID
+    async fn restore(&self, id: ID) -> RepositoryResult<Option<T>>
+    where
+        Self: Sized,
+    {
+        let mut conn = self.pool().acquire().await?;
+        self.restore_in(&mut conn, id).await
+    }
+
+    /// Permanently delete an entity by ID (ignores soft delete setting)
+    async fn hard_delete(&self, id: ID) -> RepositoryResult<bool> {
+        let mut conn = self.pool().acquire().await?;
+        self.hard_delete_in(&mut conn, id).await
+    }
+
+    /// Count entities matching the given search parameters
+    async fn count(&self, params: SearchParams) -> RepositoryResult<i64> {
+        let mut conn = self.pool().acquire().await?;
+        self.count_in(&mut conn, params).await
+    }
+
+    /// Compute aggregates over the entities matching the given search parameters
+    async fn aggregate(&self, params: SearchParams, query: AggregateQuery) -> RepositoryResult<Vec<AggregateRow>> {
+        let mut conn = self.pool().acquire().await?;
+        self.aggregate_in(&mut conn, params, query).await
+    }
+
+    /// Distinct values of `field` among the entities matching the given search parameters, with their counts
+    async fn distinct_values(&self, field: &str, params: SearchParams) -> RepositoryResult<Vec<FacetCount>> {
+        let mut conn = self.pool().acquire().await?;
+        self.distinct_values_in(&mut conn, field, params).await
+    }
+
+    /// Up to `limit` distinct values of the text `field` starting with `prefix`, among the entities matching `params`
+    async fn suggest(&self, field: &str, prefix: &str, limit: u32, params: SearchParams) -> RepositoryResult<Vec<String>> {
+        let mut conn = self.pool().acquire().await?;
+        self.suggest_in(&mut conn, field, prefix, limit, params).await
+    }
+
+    /// Count entities matching the given search parameters by each of `params.facets`
+    async fn facets(&self, params: SearchParams) -> RepositoryResult<HashMap<String, Vec<FacetCount>>> {
+        let mut conn = self.pool().acquire().await?;
+        self.facets_in(&mut conn, params).await
+    }
+
+    /// Check that the database supports [`MatchMode::Fuzzy`] searches
+    ///
+    /// Fails with [`RepositoryError::UnsupportedFeature`] when the backend has
+    /// no trigram search or the `pg_trgm` extension is not installed.
+    async fn check_fuzzy_search(&self) -> RepositoryResult<()> {
+        let Some(query) = B::fuzzy_search_check_sql() else {
+            return Err(RepositoryError::unsupported_feature("fuzzy_search", B::NAME));
         };
+        let mut conn = self.pool().acquire().await?;
+        let installed: i64 = sqlx::query_scalar(query)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(RepositoryError::from)?;
+        if installed == 0 {
+            return Err(RepositoryError::unsupported_feature("pg_trgm", B::NAME));
+        }
+        Ok(())
+    }
+
+    // Connection-scoped operations using the backend's SQL dialect
+    //
+    // `conn` may be a pooled connection or an open transaction
+    // (`&mut tx` for a `sqlx::Transaction`), so several repositories can
+    // take part in the same commit or rollback.
+
+    /// Find an entity by its ID on the given connection
+    async fn find_by_id_in(&self, conn: &mut <B::Database as Database>::Connection, id: ID) -> RepositoryResult<Option<T>> {
+        let query = B::select_by_id_sql(Self::table_name(), Self::soft_delete_enabled());
 
         sqlx::query_as(&query)
             .bind(id)
-            .fetch_optional(self.pool())
+            .fetch_optional(&mut *conn)
             .await
             .map_err(RepositoryError::from)
     }
 
-    /// Find all entities
-    async fn find_all(&self) -> RepositoryResult<Vec<T>> {
-        let query = if Self::soft_delete_enabled() {
-            format!("SELECT * FROM {} WHERE deleted_at IS NULL ORDER BY id", Self::table_name())
-        } else {
-            format!("SELECT * FROM {} ORDER BY id", Self::table_name())
-        };
+    /// Find all entities on the given connection
+    async fn find_all_in(&self, conn: &mut <B::Database as Database>::Connection) -> RepositoryResult<Vec<T>> {
+        let query = B::select_all_sql(Self::table_name(), Self::soft_delete_enabled());
 
         sqlx::query_as(&query)
-            .fetch_all(self.pool())
+            .fetch_all(&mut *conn)
             .await
             .map_err(RepositoryError::from)
     }
 
-    /// Delete an entity by ID (soft delete if enabled, otherwise hard delete)
-    async fn delete(&self, id: ID) -> RepositoryResult<bool> {
-        let query = if Self::soft_delete_enabled() {
-            format!("UPDATE {} SET deleted_at = NOW(), updated_at = NOW() WHERE id = $1 AND deleted_at IS NULL", Self::table_name())
-        } else {
-            format!("DELETE FROM {} WHERE id = $1", Self::table_name())
-        };
+    /// Delete an entity by ID on the given connection
+    async fn delete_in(&self, conn: &mut <B::Database as Database>::Connection, id: ID) -> RepositoryResult<bool> {
+        let query = B::delete_sql(Self::table_name(), Self::soft_delete_enabled());
 
         let result = sqlx::query(&query)
             .bind(id)
-            .execute(self.pool())
+            .execute(&mut *conn)
             .await
             .map_err(RepositoryError::from)?;
 
-        Ok(result.rows_affected() > 0)
+        Ok(B::rows_affected(&result) > 0)
     }
 
-    /// Search entities with filtering, pagination, and sorting
-    async fn search(&self, params: SearchParams) -> RepositoryResult<SearchResult<T>> {
-        let mut conditions = Vec::new();
-        let mut bind_count = 0;
-        let mut bind_values: Vec<String> = Vec::new();
-
-        // Handle soft delete scope
-        if Self::soft_delete_enabled() {
-            match params.scope {
-                RecordScope::Active => conditions.push("deleted_at IS NULL".to_string()),
-                RecordScope::Deleted => conditions.push("deleted_at IS NOT NULL".to_string()),
-                RecordScope::All => {} // No condition needed
-            }
+    /// Search entities on the given connection
+    ///
+    /// Offset pages fetch the rows and the total count in one statement.
+    async fn search_in(&self, conn: &mut <B::Database as Database>::Connection, params: SearchParams) -> RepositoryResult<SearchResult<T>> {
+        let params = page_params(params, Self::default_per_page(), Self::max_per_page(), Self::strict_params())?;
+        let (where_clause, bind_values) = build_where_clause::<B>(
+            &params,
+            Self::soft_delete_enabled(),
+            Self::searchable_fields(),
+            Self::full_text(),
+            Self::filterable_fields(),
+            Self::field_types(),
+            Self::strict_params(),
+        )?;
+
+        // The id is always the last sort key, so the order is total
+        let sort = params.sort_keys();
+        validate_sort_fields(&sort, Self::sortable_fields(), Self::field_types())?;
+        let cursor = params
+            .cursor
+            .as_deref()
+            .map(|cursor| Cursor::decode(cursor, Self::table_name(), &sort))
+            .transpose()?;
+
+        // Ranking and snippets apply when a non-empty query runs as full-text
+        // search; fuzzy searches can be ranked by similarity instead
+        let fuzzy = params.match_mode == MatchMode::Fuzzy;
+        if (params.headlines || (params.order_by_rank && !fuzzy)) && Self::full_text().is_none() {
+            return Err(RepositoryError::configuration(
+                "order_by_rank and headlines need #[repository(full_text(...))]",
+            ));
+        }
+        let text_query = params.query.as_deref().filter(|query| !query.trim().is_empty());
+        let full_text_query = Self::full_text().filter(|_| !fuzzy).zip(text_query);
+        let fuzzy_query = text_query.filter(|_| fuzzy && !Self::searchable_fields().is_empty());
+        let ranked = params.order_by_rank && (full_text_query.is_some() || fuzzy_query.is_some());
+        if ranked && cursor.is_some() {
+            return Err(RepositoryError::validation(
+                "Cursor pagination cannot be combined with order_by_rank",
+            ));
         }
 
-        // Handle text search
-        if let Some(query) = &params.query {
-            if !Self::searchable_fields().is_empty() && !query.trim().is_empty() {
-                bind_count += 1;
-                let search_conditions = Self::searchable_fields()
-                    .iter()
-                    .map(|field| format!("{} ILIKE ${}", field, bind_count))
-                    .collect::<Vec<_>>()
-                    .join(" OR ");
-                conditions.push(format!("({})", search_conditions));
-                bind_values.push(format!("%{}%", query));
+        // A cursor seeks past its row instead of skipping `page * per_page` rows,
+        // reading the sort order backwards when paging to earlier rows
+        let mut page_where = where_clause.clone();
+        let mut page_values = bind_values.clone();
+        let (order_by, offset) = match &cursor {
+            Some(cursor) => {
+                let condition = cursor.condition::<B>(Self::field_types(), &mut page_values)?;
+                page_where.push_str(if page_where.is_empty() { " WHERE " } else { " AND " });
+                page_where.push_str(&condition);
+                let order_by: Vec<_> = if cursor.is_before() {
+                    sort.iter().map(SortKey::reverse).collect()
+                } else {
+                    sort.clone()
+                };
+                (order_by, None)
+            }
+            None => (sort.clone(), Some(params.page * params.per_page)),
+        };
+
+        // Relevance goes before the sort keys; snippets are extra columns. Offset
+        // pages count every match alongside the rows, in the same statement
+        let mut order_terms: Vec<String> = order_by.iter().map(B::order_by_sql).collect();
+        let mut extra_columns = Vec::new();
+        let windowed_count = cursor.is_none() && params.count_mode == CountMode::Exact;
+        if windowed_count {
+            extra_columns.push(format!("COUNT(*) OVER() AS {}", B::quote_identifier(TOTAL_COUNT_COLUMN)));
+        }
+        if let (Some(query), true) = (fuzzy_query, params.order_by_rank) {
+            page_values.push(SqlValue::Text(query.to_string()));
+            let score = B::similarity_score_sql(Self::searchable_fields(), &B::placeholder(page_values.len()))?;
+            order_terms.insert(0, format!("{} DESC", score));
+        }
+        if let Some((full_text, query)) = full_text_query {
+            if params.order_by_rank {
+                page_values.push(SqlValue::Text(query.to_string()));
+                let rank = B::full_text_rank_sql(full_text.config, full_text.fields, &B::placeholder(page_values.len()))?;
+                order_terms.insert(0, format!("{} DESC", rank));
+            }
+            if params.headlines {
+                for field in full_text.fields {
+                    page_values.push(SqlValue::Text(query.to_string()));
+                    let headline = B::full_text_headline_sql(full_text.config, field, &B::placeholder(page_values.len()))?;
+                    extra_columns.push(format!("{} AS {}", headline, B::quote_identifier(&headline_column(field))));
+                }
             }
         }
 
-        // Handle field filters
-        for (field, value) in &params.filters {
-            if Self::filterable_fields().contains(&field.as_str()) {
-                bind_count += 1;
-                conditions.push(format!("{} = ${}", field, bind_count));
-                bind_values.push(value.clone());
+        // Fetch one extra row to learn whether another page follows
+        let page_query = B::select_page_sql(
+            Self::table_name(),
+            &extra_columns,
+            &page_where,
+            &order_terms,
+            params.per_page.saturating_add(1),
+            offset,
+        );
+        let mut rows = sqlx::query_with(&page_query, bind_arguments::<B>(&page_values)?)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|err| search_error::<B>(err, &params))?;
+
+        let has_more = rows.len() > params.per_page as usize;
+        rows.truncate(params.per_page as usize);
+
+        // The last offset page pins the total down in any mode. Otherwise an empty
+        // page carries no windowed count, and a cursor's seek condition would
+        // narrow it, so those count in a query of their own
+        let seen = offset.unwrap_or(0) as i64 + rows.len() as i64;
+        let last_page = offset.is_some_and(|offset| !has_more && (offset == 0 || !rows.is_empty()));
+        let (total_count, count_mode) = match params.count_mode {
+            _ if last_page => (seen, CountMode::Exact),
+            CountMode::Exact => match rows.first() {
+                Some(row) if windowed_count => match B::decode_value(row, TOTAL_COUNT_COLUMN, FieldType::I64)? {
+                    Some(SqlValue::I64(count)) => (count, CountMode::Exact),
+                    _ => return Err(sqlx::Error::ColumnNotFound(TOTAL_COUNT_COLUMN.to_string()).into()),
+                },
+                _ => {
+                    let count_query = B::count_sql(Self::table_name(), &where_clause);
+                    let count: i64 = sqlx::query_scalar_with(&count_query, bind_arguments::<B>(&bind_values)?)
+                        .fetch_one(&mut *conn)
+                        .await
+                        .map_err(|err| search_error::<B>(err, &params))?;
+                    (count, CountMode::Exact)
+                }
+            },
+            CountMode::Estimated => {
+                let plan_query = B::estimated_count_sql(Self::table_name(), &where_clause)?;
+                let plan = sqlx::query_with(&plan_query, bind_arguments::<B>(&bind_values)?)
+                    .fetch_one(&mut *conn)
+                    .await
+                    .map_err(|err| search_error::<B>(err, &params))?;
+                let column = plan.columns().first().map(|column| column.name().to_string()).unwrap_or_default();
+                let estimate = match B::decode_value(&plan, &column, FieldType::Text)? {
+                    Some(SqlValue::Text(plan)) => plan_rows_estimate(&plan)
+                        .ok_or_else(|| sqlx::Error::Decode(format!("No row estimate in plan: {}", plan).into()))?,
+                    _ => return Err(sqlx::Error::ColumnNotFound(column).into()),
+                };
+                // Rows already fetched exist, whatever the planner thinks
+                (estimate.max(seen), CountMode::Estimated)
             }
+            CountMode::None => (seen, CountMode::None),
+        };
+        let backwards = cursor.as_ref().is_some_and(Cursor::is_before);
+        if backwards {
+            rows.reverse();
         }
 
-        let where_clause = if conditions.is_empty() {
-            String::new()
-        } else {
-            format!(" WHERE {}", conditions.join(" AND "))
+        // Rows exist past the last row when more were fetched going forwards or we
+        // came back from there; before the first row, likewise in reverse
+        let (more_after, more_before) = match &cursor {
+            Some(_) if backwards => (true, has_more),
+            Some(_) => (has_more, true),
+            None => (has_more, params.page > 0),
+        };
+        let cursor_at = |row: Option<&<B::Database as Database>::Row>, wanted: bool, before: bool| {
+            match row {
+                // Rank is not a column, so ranked pages can't be resumed from a row
+                Some(row) if wanted && !ranked && !Self::field_types().is_empty() => {
+                    cursor_for_row::<B>(row, Self::table_name(), &sort, Self::field_types(), before).map(Some)
+                }
+                _ => Ok(None),
+            }
         };
+        let next_cursor = cursor_at(rows.last(), more_after, false)?;
+        let prev_cursor = cursor_at(rows.first(), more_before, true)?;
 
-        // Build count query
-        let count_query = format!("SELECT COUNT(*) FROM {}{}", Self::table_name(), where_clause);
-        
-        // Build main query with sorting and pagination
-        let sort_field = params.sort_by.as_deref().unwrap_or("id");
-        let sort_order = match params.sort_order {
-            SortOrder::Asc => "ASC",
-            SortOrder::Desc => "DESC",
+        let items = rows
+            .iter()
+            .map(|row| T::from_row(row))
+            .collect::<Result<Vec<T>, _>>()
+            .map_err(RepositoryError::from)?;
+
+        let headlines = match full_text_query {
+            Some((full_text, _)) if params.headlines => rows
+                .iter()
+                .map(|row| {
+                    let mut snippets = HashMap::new();
+                    for field in full_text.fields {
+                        if let Some(SqlValue::Text(snippet)) = B::decode_value(row, &headline_column(field), FieldType::Text)? {
+                            snippets.insert(field.to_string(), snippet);
+                        }
+                    }
+                    Ok(snippets)
+                })
+                .collect::<RepositoryResult<Vec<_>>>()?,
+            _ => Vec::new(),
         };
-        let offset = params.page * params.per_page;
 
-        let main_query = format!(
-            "SELECT * FROM {}{} ORDER BY {} {} LIMIT {} OFFSET {}",
-            Self::table_name(),
-            where_clause,
-            sort_field,
-            sort_order,
-            params.per_page,
-            offset
-        );
+        let facets = if params.facets.is_empty() {
+            HashMap::new()
+        } else {
+            self.facets_in(&mut *conn, params.clone()).await?
+        };
 
-        // Execute queries
-        let mut count_query_builder = sqlx::query_scalar(&count_query);
-        let mut main_query_builder = sqlx::query_as(&main_query);
+        Ok(SearchResult::new(items, total_count, params.page, params.per_page)
+            .with_cursors(next_cursor, prev_cursor)
+            .with_count_mode(count_mode, more_after)
+            .with_headlines(headlines)
+            .with_facets(facets))
+    }
 
-        for value in &bind_values {
-            count_query_builder = count_query_builder.bind(value);
-            main_query_builder = main_query_builder.bind(value);
+    /// Count entities matching the given search parameters by each of `params.facets` on the given connection
+    async fn facets_in(
+        &self,
+        conn: &mut <B::Database as Database>::Connection,
+        params: SearchParams,
+    ) -> RepositoryResult<HashMap<String, Vec<FacetCount>>> {
+        let mut facets = HashMap::new();
+        for field in &params.facets {
+            if !Self::filterable_fields().contains(&field.as_str()) {
+                return Err(RepositoryError::validation(format!("Field '{}' is not filterable", field)));
+            }
+
+            // Leave out the facet's own selection so its other values still count
+            let mut facet_params = params.clone();
+            facet_params.filters.retain(|key, _| {
+                key != field
+                    && range_bound(key, Self::filterable_fields(), Self::field_types()).map(|(f, _)| f) != Some(field.as_str())
+            });
+            facet_params.conditions.retain(|filter| filter.field != *field);
+            facet_params.filter = params.filter.as_ref().and_then(|filter| filter.without_field(field));
+            let (where_clause, bind_values) = build_where_clause::<B>(
+                &facet_params,
+                Self::soft_delete_enabled(),
+                Self::searchable_fields(),
+                Self::full_text(),
+                Self::filterable_fields(),
+                Self::field_types(),
+                Self::strict_params(),
+            )?;
+
+            let facet_query = B::facet_sql(Self::table_name(), field, &where_clause);
+            let rows = sqlx::query_with(&facet_query, bind_arguments::<B>(&bind_values)?)
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(|err| search_error::<B>(err, &params))?;
+
+            let counts = decode_value_counts::<B>(&rows, field_type_of(Self::field_types(), field))?;
+            facets.insert(field.clone(), counts);
         }
+        Ok(facets)
+    }
 
-        let total_count: i64 = count_query_builder
-            .fetch_one(self.pool())
+    /// Compute aggregates over the entities matching the given search parameters on the given connection
+    ///
+    /// Only the text query, scope and filters of `params` apply.
+    async fn aggregate_in(
+        &self,
+        conn: &mut <B::Database as Database>::Connection,
+        params: SearchParams,
+        query: AggregateQuery,
+    ) -> RepositoryResult<Vec<AggregateRow>> {
+        if query.aggregates.is_empty() {
+            return Err(RepositoryError::validation("An aggregate query needs at least one aggregate"));
+        }
+        let mut names = HashSet::new();
+        for aggregate in &query.aggregates {
+            aggregate.validate(Self::aggregatable_fields(), Self::field_types())?;
+            // Each name keys one value in the result rows
+            let name = aggregate.name();
+            if !names.insert(name.clone()) {
+                return Err(RepositoryError::validation(format!("Aggregate name '{}' is used more than once", name)));
+            }
+        }
+        for field in &query.group_by {
+            if !Self::filterable_fields().contains(&field.as_str()) {
+                return Err(RepositoryError::validation(format!("Field '{}' is not filterable", field)));
+            }
+        }
+
+        let (where_clause, bind_values) = build_where_clause::<B>(
+            &params,
+            Self::soft_delete_enabled(),
+            Self::searchable_fields(),
+            Self::full_text(),
+            Self::filterable_fields(),
+            Self::field_types(),
+            Self::strict_params(),
+        )?;
+
+        // Aggregates are selected under positional aliases, so names chosen by
+        // the caller never reach the SQL
+        let field_types: Vec<FieldType> = query
+            .aggregates
+            .iter()
+            .map(|aggregate| {
+                aggregate
+                    .field
+                    .as_deref()
+                    .map_or(FieldType::I64, |field| field_type_of(Self::field_types(), field))
+            })
+            .collect();
+        let mut columns: Vec<String> = query.group_by.iter().map(|field| B::quote_identifier(field)).collect();
+        for (index, (aggregate, field_type)) in query.aggregates.iter().zip(&field_types).enumerate() {
+            let term = B::aggregate_term_sql(aggregate.func, aggregate.field.as_deref(), *field_type);
+            columns.push(format!("{} AS {}", term, B::quote_identifier(&aggregate_column(index))));
+        }
+
+        let aggregate_query = B::aggregate_sql(Self::table_name(), &columns, &where_clause, &query.group_by);
+        let rows = sqlx::query_with(&aggregate_query, bind_arguments::<B>(&bind_values)?)
+            .fetch_all(&mut *conn)
             .await
-            .map_err(RepositoryError::from)?;
-            
-        let items: Vec<T> = main_query_builder
-            .fetch_all(self.pool())
+            .map_err(|err| search_error::<B>(err, &params))?;
+
+        rows.iter()
+            .map(|row| {
+                let mut group = HashMap::new();
+                for field in &query.group_by {
+                    let value = B::decode_value(row, field, field_type_of(Self::field_types(), field))?;
+                    group.insert(field.clone(), value.as_ref().map(FilterValue::from));
+                }
+                let mut values = HashMap::new();
+                for (index, (aggregate, field_type)) in query.aggregates.iter().zip(&field_types).enumerate() {
+                    let value = B::decode_value(row, &aggregate_column(index), aggregate.func.result_type(*field_type))?;
+                    values.insert(aggregate.name(), value.as_ref().map(FilterValue::from));
+                }
+                Ok(AggregateRow { group, values })
+            })
+            .collect()
+    }
+
+    /// Distinct values of `field` among the entities matching the given search parameters on the given connection
+    ///
+    /// Returns page `params.page` of `params.per_page` values in
+    /// `params.value_order`, each with the number of matches holding it.
+    /// `field` must be searchable or filterable.
+    async fn distinct_values_in(
+        &self,
+        conn: &mut <B::Database as Database>::Connection,
+        field: &str,
+        params: SearchParams,
+    ) -> RepositoryResult<Vec<FacetCount>> {
+        validate_value_field(field, Self::searchable_fields(), Self::filterable_fields())?;
+        // Values page by offset only, so the page must be in range even with a cursor set
+        let params = SearchParams { cursor: None, ..params };
+        let params = page_params(params, Self::default_per_page(), Self::max_per_page(), Self::strict_params())?;
+        let (where_clause, bind_values) = build_where_clause::<B>(
+            &params,
+            Self::soft_delete_enabled(),
+            Self::searchable_fields(),
+            Self::full_text(),
+            Self::filterable_fields(),
+            Self::field_types(),
+            Self::strict_params(),
+        )?;
+
+        let values_query = B::distinct_values_sql(
+            Self::table_name(),
+            field,
+            &where_clause,
+            params.value_order,
+            Some(params.per_page),
+            params.page * params.per_page,
+        );
+        let rows = sqlx::query_with(&values_query, bind_arguments::<B>(&bind_values)?)
+            .fetch_all(&mut *conn)
             .await
-            .map_err(RepositoryError::from)?;
+            .map_err(|err| search_error::<B>(err, &params))?;
+        decode_value_counts::<B>(&rows, field_type_of(Self::field_types(), field))
+    }
 
-        Ok(SearchResult::new(items, total_count, params.page, params.per_page))
+    /// Up to `limit` distinct values of the text `field` starting with `prefix` on the given connection
+    ///
+    /// The prefix matches regardless of case. Values of the entities matching
+    /// the text query, scope and filters of `params` are considered, most
+    /// common first; `field` must be searchable or filterable. `limit` is checked like a
+    /// `per_page`: 0 means the default page size, and the maximum applies.
+    async fn suggest_in(
+        &self,
+        conn: &mut <B::Database as Database>::Connection,
+        field: &str,
+        prefix: &str,
+        limit: u32,
+        params: SearchParams,
+    ) -> RepositoryResult<Vec<String>> {
+        validate_value_field(field, Self::searchable_fields(), Self::filterable_fields())?;
+        if field_type_of(Self::field_types(), field) != FieldType::Text {
+            return Err(RepositoryError::validation(format!("Field '{}' is not a text field", field)));
+        }
+        let params = SearchParams {
+            page: 0,
+            per_page: limit,
+            ..params
+        };
+        let params = page_params(params, Self::default_per_page(), Self::max_per_page(), Self::strict_params())?;
+        let (mut where_clause, mut bind_values) = build_where_clause::<B>(
+            &params,
+            Self::soft_delete_enabled(),
+            Self::searchable_fields(),
+            Self::full_text(),
+            Self::filterable_fields(),
+            Self::field_types(),
+            Self::strict_params(),
+        )?;
+
+        let column = B::quote_identifier(field);
+        let condition = if prefix.is_empty() {
+            format!("{} IS NOT NULL", column)
+        } else {
+            bind_values.push(SqlValue::Text(B::match_pattern(prefix, MatchMode::Prefix)));
+            B::match_sql(&column, &B::placeholder(bind_values.len()), MatchMode::Prefix)
+        };
+        where_clause.push_str(if where_clause.is_empty() { " WHERE " } else { " AND " });
+        where_clause.push_str(&condition);
+
+        let values_query = B::distinct_values_sql(
+            Self::table_name(),
+            field,
+            &where_clause,
+            ValueOrder::Frequency,
+            Some(params.per_page),
+            0,
+        );
+        let rows = sqlx::query_with(&values_query, bind_arguments::<B>(&bind_values)?)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|err| search_error::<B>(err, &params))?;
+        Ok(decode_value_counts::<B>(&rows, FieldType::Text)?
+            .into_iter()
+            .filter_map(|value_count| match value_count.value {
+                Some(FilterValue::Text(value)) => Some(value),
+                _ => None,
+            })
+            .collect())
     }
 
-    /// Restore a soft-deleted entity by ID
-    async fn restore(&self, id: ID) -> RepositoryResult<Option<T>>
+    /// Restore a soft-deleted entity by ID on the given connection
+    async fn restore_in(&self, conn: &mut <B::Database as Database>::Connection, id: ID) -> RepositoryResult<Option<T>>
     where
         Self: Sized,
     {
@@ -184,82 +674,308 @@ where
             return Err(RepositoryError::configuration("Soft delete not enabled"));
         }
 
-        let query = format!(
-            "UPDATE {} SET deleted_at = NULL, updated_at = NOW() WHERE id = $1 RETURNING *",
-            Self::table_name()
-        );
+        let query = B::restore_sql(Self::table_name());
 
-        sqlx::query_as(&query)
+        if B::SUPPORTS_RETURNING {
+            return sqlx::query_as(&query)
+                .bind(id)
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(RepositoryError::from);
+        }
+
+        // Without RETURNING, re-select the row (regardless of soft delete state)
+        let result = sqlx::query(&query)
+            .bind(id.clone())
+            .execute(&mut *conn)
+            .await
+            .map_err(RepositoryError::from)?;
+
+        if B::rows_affected(&result) == 0 {
+            return Ok(None);
+        }
+
+        sqlx::query_as(&B::select_by_id_sql(Self::table_name(), false))
             .bind(id)
-            .fetch_optional(self.pool())
+            .fetch_optional(&mut *conn)
             .await
             .map_err(RepositoryError::from)
     }
 
-    /// Permanently delete an entity by ID (ignores soft delete setting)
-    async fn hard_delete(&self, id: ID) -> RepositoryResult<bool> {
-        let result = sqlx::query(&format!("DELETE FROM {} WHERE id = $1", Self::table_name()))
+    /// Permanently delete an entity by ID on the given connection
+    async fn hard_delete_in(&self, conn: &mut <B::Database as Database>::Connection, id: ID) -> RepositoryResult<bool> {
+        let result = sqlx::query(&B::delete_sql(Self::table_name(), false))
             .bind(id)
-            .execute(self.pool())
+            .execute(&mut *conn)
             .await
             .map_err(RepositoryError::from)?;
 
-        Ok(result.rows_affected() > 0)
+        Ok(B::rows_affected(&result) > 0)
     }
 
-    /// Count entities matching the given search parameters
-    async fn count(&self, params: SearchParams) -> RepositoryResult<i64> {
-        let mut conditions = Vec::new();
-        let mut bind_values: Vec<String> = Vec::new();
-
-        // Handle soft delete scope
-        if Self::soft_delete_enabled() {
-            match params.scope {
-                RecordScope::Active => conditions.push("deleted_at IS NULL".to_string()),
-                RecordScope::Deleted => conditions.push("deleted_at IS NOT NULL".to_string()),
-                RecordScope::All => {} // No condition needed
-            }
+    /// Count entities matching the given search parameters on the given connection
+    async fn count_in(&self, conn: &mut <B::Database as Database>::Connection, params: SearchParams) -> RepositoryResult<i64> {
+        let params = page_params(params, Self::default_per_page(), Self::max_per_page(), Self::strict_params())?;
+        let (where_clause, bind_values) = build_where_clause::<B>(
+            &params,
+            Self::soft_delete_enabled(),
+            Self::searchable_fields(),
+            Self::full_text(),
+            Self::filterable_fields(),
+            Self::field_types(),
+            Self::strict_params(),
+        )?;
+
+        let count_query = B::count_sql(Self::table_name(), &where_clause);
+        let args = bind_arguments::<B>(&bind_values)?;
+
+        sqlx::query_scalar_with(&count_query, args)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|err| search_error::<B>(err, &params))
+    }
+}
+
+/// Build the WHERE clause shared by `search` and `count`
+///
+/// Returns the clause (empty or starting with ` WHERE`) and the values to
+/// bind, in placeholder order, each parsed as its column's type. Each
+/// placeholder is used exactly once so the clause works with backends that
+/// only support positional `?` parameters.
+fn build_where_clause<B: DatabaseBackend>(
+    params: &SearchParams,
+    soft_delete: bool,
+    searchable_fields: &[&str],
+    full_text: Option<FullTextConfig>,
+    filterable_fields: &[&str],
+    field_types: &[(&str, FieldType)],
+    strict: bool,
+) -> RepositoryResult<(String, Vec<SqlValue>)> {
+    let mut conditions = Vec::new();
+    let mut bind_values: Vec<SqlValue> = Vec::new();
+
+    if strict {
+        if !soft_delete && matches!(params.scope, RecordScope::Deleted) {
+            return Err(RepositoryError::validation(
+                "Scope 'deleted' needs soft delete (allowed: 'active', 'all')",
+            ));
+        }
+        let mut unknown: Vec<&str> = params
+            .filters
+            .keys()
+            .map(String::as_str)
+            .filter(|key| range_bound(key, filterable_fields, field_types).is_none())
+            .chain(params.conditions.iter().map(|filter| filter.field.as_str()))
+            .filter(|field| !filterable_fields.contains(field))
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort_unstable();
+            unknown.dedup();
+            return Err(unknown_fields_error("filter", &unknown, filterable_fields));
         }
+    }
 
-        // Handle text search
-        if let Some(query) = &params.query {
-            if !Self::searchable_fields().is_empty() && !query.trim().is_empty() {
-                let search_conditions = Self::searchable_fields()
-                    .iter()
-                    .map(|field| format!("{} ILIKE $1", field))
-                    .collect::<Vec<_>>()
-                    .join(" OR ");
-                conditions.push(format!("({})", search_conditions));
-                bind_values.push(format!("%{}%", query));
-            }
+    // Handle soft delete scope
+    if soft_delete {
+        match params.scope {
+            RecordScope::Active => conditions.push(format!("{} IS NULL", B::quote_identifier("deleted_at"))),
+            RecordScope::Deleted => conditions.push(format!("{} IS NOT NULL", B::quote_identifier("deleted_at"))),
+            RecordScope::All => {} // No condition needed
         }
+    }
 
-        // Handle field filters
-        let mut bind_count = if bind_values.is_empty() { 0 } else { 1 };
-        for (field, value) in &params.filters {
-            if Self::filterable_fields().contains(&field.as_str()) {
-                bind_count += 1;
-                conditions.push(format!("{} = ${}", field, bind_count));
-                bind_values.push(value.clone());
+    // Handle text search
+    if let Some(query) = &params.query {
+        if params.match_mode == MatchMode::Fuzzy {
+            if !searchable_fields.is_empty() && !query.trim().is_empty() {
+                bind_values.push(SqlValue::Text(query.clone()));
+                let placeholder = B::placeholder(bind_values.len());
+                let threshold = match params.similarity_threshold {
+                    Some(threshold) if !(0.0..=1.0).contains(&threshold) => {
+                        return Err(RepositoryError::validation(format!(
+                            "similarity_threshold must be between 0 and 1, got {}",
+                            threshold
+                        )));
+                    }
+                    Some(threshold) => {
+                        bind_values.push(SqlValue::F32(threshold));
+                        Some(B::placeholder(bind_values.len()))
+                    }
+                    None => None,
+                };
+                conditions.push(B::similarity_match_sql(searchable_fields, &placeholder, threshold.as_deref())?);
             }
+        } else if let (Some(full_text), false) = (full_text, query.trim().is_empty()) {
+            bind_values.push(SqlValue::Text(query.clone()));
+            conditions.push(B::full_text_match_sql(
+                full_text.config,
+                full_text.fields,
+                &B::placeholder(bind_values.len()),
+            )?);
+        } else if !searchable_fields.is_empty() && !query.trim().is_empty() {
+            let pattern = B::match_pattern(query, params.match_mode);
+            let search_conditions = searchable_fields
+                .iter()
+                .map(|field| {
+                    bind_values.push(SqlValue::Text(pattern.clone()));
+                    B::match_sql(&B::quote_identifier(field), &B::placeholder(bind_values.len()), params.match_mode)
+                })
+                .collect::<Vec<_>>()
+                .join(" OR ");
+            conditions.push(format!("({})", search_conditions));
         }
+    }
 
-        let where_clause = if conditions.is_empty() {
-            String::new()
-        } else {
-            format!(" WHERE {}", conditions.join(" AND "))
-        };
-
-        let count_query = format!("SELECT COUNT(*) FROM {}{}", Self::table_name(), where_clause);
-        let mut query_builder = sqlx::query_scalar(&count_query);
+    // Handle field filters and `{field}_from` / `{field}_to` range bounds
+    for (field, value) in &params.filters {
+        if filterable_fields.contains(&field.as_str()) {
+            bind_values.push(field_type_of(field_types, field).parse(field, &FilterValue::Text(value.clone()))?);
+            conditions.push(format!("{} = {}", B::quote_identifier(field), B::placeholder(bind_values.len())));
+        } else if let Some((field, bound)) = range_bound(field, filterable_fields, field_types) {
+            let filter = Filter::new(field, bound(FilterValue::Text(value.clone())));
+            conditions.push(filter.to_sql::<B>(field_type_of(field_types, field), &mut bind_values)?);
+        }
+    }
 
-        for value in &bind_values {
-            query_builder = query_builder.bind(value);
+    // Handle typed filter conditions
+    for filter in &params.conditions {
+        if filterable_fields.contains(&filter.field.as_str()) {
+            conditions.push(filter.to_sql::<B>(field_type_of(field_types, &filter.field), &mut bind_values)?);
         }
+    }
 
-        query_builder
-            .fetch_one(self.pool())
-            .await
-            .map_err(RepositoryError::from)
+    // Handle the filter expression tree
+    if let Some(filter) = &params.filter {
+        filter.validate(filterable_fields)?;
+        conditions.push(filter.to_sql::<B>(field_types, &mut bind_values)?);
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    };
+
+    Ok((where_clause, bind_values))
+}
+
+
+/// Check that `field` may be listed by `distinct_values` and `suggest`
+fn validate_value_field(field: &str, searchable: &[&str], filterable: &[&str]) -> RepositoryResult<()> {
+    if searchable.contains(&field) || filterable.contains(&field) {
+        Ok(())
+    } else {
+        Err(RepositoryError::validation(format!(
+            "Field '{}' is not searchable or filterable",
+            field
+        )))
+    }
+}
+
+/// Read the `value` and `count` columns of distinct-value rows
+fn decode_value_counts<B: DatabaseBackend>(
+    rows: &[<B::Database as Database>::Row],
+    field_type: FieldType,
+) -> RepositoryResult<Vec<FacetCount>> {
+    rows.iter()
+        .map(|row| {
+            let value = B::decode_value(row, "value", field_type)?;
+            let count = match B::decode_value(row, "count", FieldType::I64)? {
+                Some(SqlValue::I64(count)) => count,
+                _ => return Err(sqlx::Error::ColumnNotFound("count".to_string()).into()),
+            };
+            Ok(FacetCount {
+                value: value.as_ref().map(FilterValue::from),
+                count,
+            })
+        })
+        .collect()
+}
+
+/// Name of the column carrying the aggregate at `index` of an aggregate query
+fn aggregate_column(index: usize) -> String {
+    format!("__aggregate_{}", index)
+}
+
+/// Name of the extra column carrying the number of rows matching a search
+const TOTAL_COUNT_COLUMN: &str = "__total_count";
+
+/// Name of the extra column carrying the full-text snippet of `field`
+fn headline_column(field: &str) -> String {
+    format!("__headline_{}", field)
+}
+
+/// Check that every sort key names a column the entity may be sorted by
+///
+/// The columns are spliced into `ORDER BY`, so only entity fields listed in
+/// `sortable_fields` (or any field when that list is empty) are accepted.
+/// The id is always allowed, being the default sort and the tie-breaker.
+fn validate_sort_fields(keys: &[SortKey], sortable: &[&str], field_types: &[(&str, FieldType)]) -> RepositoryResult<()> {
+    let mut allowed: Vec<&str> = if sortable.is_empty() {
+        field_types.iter().map(|(name, _)| *name).collect()
+    } else {
+        sortable.to_vec()
+    };
+    if !allowed.contains(&"id") {
+        allowed.insert(0, "id");
+    }
+    let unknown: Vec<&str> = keys
+        .iter()
+        .map(|key| key.field.as_str())
+        .filter(|field| !allowed.contains(field))
+        .collect();
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(unknown_fields_error("sort", &unknown, &allowed))
+    }
+}
+
+/// Fill in the page size of `params` and check it against the repository's limits
+///
+/// In strict mode a `per_page` above the maximum is rejected rather than lowered.
+fn page_params(
+    mut params: SearchParams,
+    default_per_page: u32,
+    max_per_page: u32,
+    strict: bool,
+) -> RepositoryResult<SearchParams> {
+    if !strict || params.per_page <= max_per_page {
+        params.normalize(default_per_page, max_per_page);
+    }
+    params.validate(max_per_page)?;
+    Ok(params)
+}
+
+/// Validation error naming the `unknown` fields of some `kind` and the `allowed` ones
+fn unknown_fields_error(kind: &str, unknown: &[&str], allowed: &[&str]) -> RepositoryError {
+    let quote = |fields: &[&str]| fields.iter().map(|field| format!("'{}'", field)).collect::<Vec<_>>().join(", ");
+    RepositoryError::validation(format!(
+        "Unknown {} field{} {} (allowed: {})",
+        kind,
+        if unknown.len() == 1 { "" } else { "s" },
+        quote(unknown),
+        if allowed.is_empty() { "none".to_string() } else { quote(allowed) }
+    ))
+}
+
+/// Map a failed search query to an error, reporting a missing `pg_trgm`
+/// extension (undefined `similarity()` or `%`) as an unsupported feature
+fn search_error<B: DatabaseBackend>(err: sqlx::Error, params: &SearchParams) -> RepositoryError {
+    let err = RepositoryError::from(err);
+    if params.match_mode == MatchMode::Fuzzy && err.sqlstate().as_deref() == Some(UNDEFINED_FUNCTION) {
+        return RepositoryError::unsupported_feature("pg_trgm", B::NAME);
+    }
+    err
+}
+
+/// Collect `values` into bind arguments for the backend's driver
+fn bind_arguments<'q, B: DatabaseBackend>(
+    values: &[SqlValue],
+) -> RepositoryResult<<B::Database as Database>::Arguments<'q>> {
+    let mut args = <B::Database as Database>::Arguments::default();
+    for value in values {
+        B::bind_value(&mut args, value)?;
     }
-}
\ No newline at end of file
+    Ok(args)
+}
@@ -0,0 +1,70 @@
+//! Retry policy for transactions that fail with transient errors
+//!
+//! Used by [`UnitOfWork::run_with_retry`](crate::UnitOfWork::run_with_retry)
+//! to rerun transactions aborted by serialization failures or deadlocks
+//! (see [`RepositoryError::is_retryable`](crate::RepositoryError::is_retryable)).
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// How often and how quickly to retry a failed transaction
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first (at least 1)
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound for any single delay
+    pub max_backoff: Duration,
+    /// Factor the delay grows by after each retry
+    pub multiplier: f64,
+    /// Randomize each delay between half and all of its value, so competing
+    /// transactions do not retry in lockstep
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based), before jitter is applied
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        // A negative or NaN multiplier yields no delay, and one too long for
+        // a Duration is capped like any other
+        let delay = (self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent)).max(0.0);
+        Duration::try_from_secs_f64(delay).map_or(self.max_backoff, |delay| delay.min(self.max_backoff))
+    }
+
+    /// Delay to actually wait before retry number `retry`, with jitter if enabled
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        if !self.jitter {
+            return backoff;
+        }
+
+        // Successive RandomStates get different (though related) keys, so the
+        // hash is spread enough for jitter, just not suitable for secrets
+        let random = RandomState::new().build_hasher().finish();
+        let fraction = 0.5 + (random as f64 / u64::MAX as f64) / 2.0;
+        Duration::try_from_secs_f64(backoff.as_secs_f64() * fraction).unwrap_or(backoff)
+    }
+}
@@ -3,63 +3,358 @@
 //! This module provides flexible search capabilities including pagination,
 //! filtering, and sorting for repository queries.
 
-use serde::{Deserialize, Serialize};
+use crate::error::{RepositoryError, RepositoryResult};
+use crate::filter::{Filter, FilterExpr, FilterValue};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 /// Parameters for searching and filtering repository queries
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct SearchParams {
     /// Text query for full-text search across searchable fields
     pub query: Option<String>,
+    /// How `query` matches the searchable fields
+    ///
+    /// Repositories with full-text search parse `query` as web-search syntax
+    /// instead, unless the mode is [`MatchMode::Fuzzy`].
+    #[serde(default)]
+    pub match_mode: MatchMode,
+    /// Minimum trigram similarity (0 to 1) for [`MatchMode::Fuzzy`]
+    ///
+    /// Unset uses the `%` operator and the server's
+    /// `pg_trgm.similarity_threshold` (0.3 by default), which trigram indexes
+    /// can serve; a value here compares `similarity()` directly.
+    #[serde(default)]
+    pub similarity_threshold: Option<f32>,
+    /// Order text matches by relevance before the sort keys
+    ///
+    /// Relevance is `ts_rank` for full-text search and trigram similarity for
+    /// [`MatchMode::Fuzzy`]. Cannot be combined with `cursor`.
+    #[serde(default)]
+    pub order_by_rank: bool,
+    /// Return snippets of each full-text field highlighting the matched terms
+    #[serde(default)]
+    pub headlines: bool,
     /// Field-specific filters (field_name -> value)
     pub filters: HashMap<String, String>,
+    /// Typed filter conditions, all of which must match
+    #[serde(default)]
+    pub conditions: Vec<Filter>,
+    /// Filter expression with nested `and` / `or` / `not` groups
+    #[serde(default)]
+    pub filter: Option<FilterExpr>,
+    /// Filterable fields to count matches by, returned as [`SearchResult::facets`]
+    ///
+    /// Each field is counted with every other filter applied but its own
+    /// `filters` entry, `conditions` and conditions in the `filter` tree, so
+    /// the counts list the alternatives to the current selection.
+    #[serde(default)]
+    pub facets: Vec<String>,
+    /// Cursor from a previous [`SearchResult`]; switches to keyset pagination
+    ///
+    /// When set, `page` is ignored and the page starts right after (or, for a
+    /// `prev_cursor`, right before) the row the cursor was taken from.
+    #[serde(default)]
+    pub cursor: Option<String>,
     /// Page number for pagination (0-based)
     pub page: u32,
-    /// Number of items per page
+    /// Number of items per page; 0 (the default) uses the repository's default
     pub per_page: u32,
+    /// How `total_count` is computed
+    #[serde(default)]
+    pub count_mode: CountMode,
+    /// Order of the values returned by `distinct_values`
+    #[serde(default)]
+    pub value_order: ValueOrder,
     /// Field to sort by (defaults to "id")
     pub sort_by: Option<String>,
     /// Sort order (ascending or descending)
     pub sort_order: SortOrder,
+    /// Sort keys applied in turn; takes precedence over `sort_by` / `sort_order`
+    ///
+    /// Deserializes from a list of [`SortKey`]s or the compact form
+    /// `-created_at,name` (see [`SortKey::parse_list`]).
+    #[serde(default, deserialize_with = "deserialize_sort_keys")]
+    pub sort: Vec<SortKey>,
     /// Record scope for soft delete handling
     pub scope: RecordScope,
 }
 
-impl Default for SearchParams {
-    fn default() -> Self {
-        Self {
-            query: None,
-            filters: HashMap::new(),
-            page: 0,
-            per_page: 10,
-            sort_by: None,
-            sort_order: SortOrder::default(),
-            scope: RecordScope::default(),
+impl SearchParams {
+    /// Sort keys the search is ordered by
+    ///
+    /// `sort` if set, otherwise `sort_by` and `sort_order`, then the id as a
+    /// tie-breaker so the order is total.
+    pub fn sort_keys(&self) -> Vec<SortKey> {
+        let mut keys = if self.sort.is_empty() {
+            vec![SortKey::new(self.sort_by.as_deref().unwrap_or("id"), self.sort_order)]
+        } else {
+            self.sort.clone()
+        };
+        if !keys.iter().any(|key| key.field == "id") {
+            let order = keys.last().map_or(SortOrder::Asc, |key| key.order);
+            keys.push(SortKey::new("id", order));
+        }
+        keys
+    }
+
+    /// Fill in and cap the page size
+    ///
+    /// A `per_page` of 0 becomes `default_per_page`, and one above
+    /// `max_per_page` is lowered to it.
+    pub fn normalize(&mut self, default_per_page: u32, max_per_page: u32) {
+        if self.per_page == 0 {
+            self.per_page = default_per_page;
         }
+        self.per_page = self.per_page.min(max_per_page);
     }
+
+    /// Check the page size and number
+    ///
+    /// `per_page` must be between 1 and `max_per_page`, and unless a `cursor`
+    /// is set, the whole page must lie below offset 2^32.
+    pub fn validate(&self, max_per_page: u32) -> RepositoryResult<()> {
+        if self.per_page == 0 || self.per_page > max_per_page {
+            return Err(RepositoryError::validation(format!(
+                "per_page must be between 1 and {}, got {}",
+                max_per_page, self.per_page
+            )));
+        }
+        let page_end = self.page.checked_add(1).and_then(|pages| pages.checked_mul(self.per_page));
+        if self.cursor.is_none() && page_end.is_none() {
+            return Err(RepositoryError::validation(format!(
+                "Page {} is out of range for {} items per page",
+                self.page, self.per_page
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// How a text query matches the searchable fields
+///
+/// `%` and `_` in the query always match literally.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    /// Field contains the query, ignoring case
+    #[default]
+    Contains,
+    /// Field starts with the query, ignoring case
+    Prefix,
+    /// Field ends with the query, ignoring case
+    Suffix,
+    /// Field equals the query, ignoring case
+    Exact,
+    /// Field contains the query, matching case
+    CaseSensitive,
+    /// Field is similar to the query, tolerating typos (PostgreSQL `pg_trgm`)
+    Fuzzy,
+}
+
+/// How a search computes [`SearchResult::total_count`]
+///
+/// A total is exact whenever the page reaches the last match, whatever the mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CountMode {
+    /// Count every match
+    #[default]
+    Exact,
+    /// Take the query planner's row estimate (PostgreSQL and MySQL 8.0.16+, not MariaDB)
+    Estimated,
+    /// Skip counting; the total is a lower bound, counting rows up to this page
+    None,
+}
+
+/// Order of distinct field values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueOrder {
+    /// Most common first, ties alphabetically
+    #[default]
+    Frequency,
+    /// Ascending by value
+    Alphabetical,
+}
+
+/// Full-text search settings from `#[repository(full_text(...))]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FullTextConfig {
+    /// Text search configuration, such as `english`
+    pub config: &'static str,
+    /// Fields whose text is searched
+    pub fields: &'static [&'static str],
 }
 
 /// Sort order for query results
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SortOrder {
     /// Ascending order (A-Z, 1-9)
+    #[default]
     Asc,
     /// Descending order (Z-A, 9-1)
     Desc,
 }
 
-impl Default for SortOrder {
-    fn default() -> Self {
-        SortOrder::Asc
+impl SortOrder {
+    /// The opposite order
+    pub fn reverse(self) -> Self {
+        match self {
+            SortOrder::Asc => SortOrder::Desc,
+            SortOrder::Desc => SortOrder::Asc,
+        }
+    }
+}
+
+/// Where `NULL`s sort relative to other values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NullsOrder {
+    /// The database's own placement
+    #[default]
+    Default,
+    /// `NULL`s before every other value
+    First,
+    /// `NULL`s after every other value
+    Last,
+}
+
+/// One column of a multi-column sort
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SortKey {
+    /// Field (column) to sort by
+    pub field: String,
+    /// Sort direction
+    #[serde(default)]
+    pub order: SortOrder,
+    /// Placement of `NULL`s
+    #[serde(default)]
+    pub nulls: NullsOrder,
+}
+
+impl SortKey {
+    /// Sort by `field` in `order`
+    pub fn new(field: impl Into<String>, order: SortOrder) -> Self {
+        Self {
+            field: field.into(),
+            order,
+            nulls: NullsOrder::Default,
+        }
+    }
+
+    /// Sort by `field` ascending
+    pub fn asc(field: impl Into<String>) -> Self {
+        Self::new(field, SortOrder::Asc)
+    }
+
+    /// Sort by `field` descending
+    pub fn desc(field: impl Into<String>) -> Self {
+        Self::new(field, SortOrder::Desc)
+    }
+
+    /// Place `NULL`s first
+    pub fn nulls_first(mut self) -> Self {
+        self.nulls = NullsOrder::First;
+        self
+    }
+
+    /// Place `NULL`s last
+    pub fn nulls_last(mut self) -> Self {
+        self.nulls = NullsOrder::Last;
+        self
+    }
+
+    /// The same key sorted the other way round, `NULL`s included
+    pub fn reverse(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            order: self.order.reverse(),
+            nulls: match self.nulls {
+                NullsOrder::Default => NullsOrder::Default,
+                NullsOrder::First => NullsOrder::Last,
+                NullsOrder::Last => NullsOrder::First,
+            },
+        }
+    }
+
+    /// Parse a comma-separated list of keys such as `-created_at:nulls_last,name`
+    ///
+    /// A leading `-` sorts descending (`+` or nothing ascending), and a
+    /// `:nulls_first` or `:nulls_last` suffix places `NULL`s.
+    pub fn parse_list(s: &str) -> RepositoryResult<Vec<Self>> {
+        if s.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        s.split(',').map(str::parse).collect()
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = RepositoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RepositoryError::validation(format!("Invalid sort key '{}'", s));
+
+        let (key, nulls) = match s.trim().split_once(':') {
+            Some((key, "nulls_first")) => (key, NullsOrder::First),
+            Some((key, "nulls_last")) => (key, NullsOrder::Last),
+            Some(_) => return Err(invalid()),
+            None => (s.trim(), NullsOrder::Default),
+        };
+        let (field, order) = match key.strip_prefix('-') {
+            Some(field) => (field, SortOrder::Desc),
+            None => (key.strip_prefix('+').unwrap_or(key), SortOrder::Asc),
+        };
+        if field.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            field: field.to_string(),
+            order,
+            nulls,
+        })
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.order == SortOrder::Desc {
+            f.write_str("-")?;
+        }
+        f.write_str(&self.field)?;
+        match self.nulls {
+            NullsOrder::Default => Ok(()),
+            NullsOrder::First => f.write_str(":nulls_first"),
+            NullsOrder::Last => f.write_str(":nulls_last"),
+        }
+    }
+}
+
+/// Accept sort keys as a list or in the compact string form
+fn deserialize_sort_keys<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<SortKey>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SortKeys {
+        Compact(String),
+        List(Vec<SortKey>),
+    }
+
+    match SortKeys::deserialize(deserializer)? {
+        SortKeys::Compact(s) => SortKey::parse_list(&s).map_err(serde::de::Error::custom),
+        SortKeys::List(keys) => Ok(keys),
     }
 }
 
 /// Record scope for handling soft-deleted records
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum RecordScope {
     /// Only active (non-deleted) records
+    #[default]
     Active,
     /// Only soft-deleted records
     Deleted,
@@ -67,12 +362,6 @@ pub enum RecordScope {
     All,
 }
 
-impl Default for RecordScope {
-    fn default() -> Self {
-        RecordScope::Active
-    }
-}
-
 /// Result container for paginated search queries
 #[derive(Debug, Serialize)]
 pub struct SearchResult<T> {
@@ -80,12 +369,35 @@ pub struct SearchResult<T> {
     pub items: Vec<T>,
     /// Total number of items matching the query (across all pages)
     pub total_count: i64,
+    /// Whether `total_count` is exact, estimated or a lower bound
+    pub count_mode: CountMode,
+    /// Whether another page follows this one
+    pub has_more: bool,
     /// Current page number (0-based)
     pub page: u32,
     /// Number of items per page
     pub per_page: u32,
     /// Total number of pages
     pub total_pages: u32,
+    /// Cursor for the page after this one, if there is one
+    pub next_cursor: Option<String>,
+    /// Cursor for the page before this one, if there is one
+    pub prev_cursor: Option<String>,
+    /// Full-text snippets for each item, by field, when `headlines` was requested
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub headlines: Vec<HashMap<String, String>>,
+    /// Match counts per value of each field requested in `facets`
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub facets: HashMap<String, Vec<FacetCount>>,
+}
+
+/// Number of matches sharing one value of a facet field
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FacetCount {
+    /// The field's value, `None` for `NULL`, in the form filters accept
+    pub value: Option<FilterValue>,
+    /// Number of matches with this value
+    pub count: i64,
 }
 
 impl<T> SearchResult<T> {
@@ -100,15 +412,50 @@ impl<T> SearchResult<T> {
         Self {
             items,
             total_count,
+            count_mode: CountMode::Exact,
+            has_more: page.saturating_add(1) < total_pages,
             page,
             per_page,
             total_pages,
+            next_cursor: None,
+            prev_cursor: None,
+            headlines: Vec::new(),
+            facets: HashMap::new(),
         }
     }
 
+    /// Attach keyset pagination cursors
+    pub fn with_cursors(mut self, next_cursor: Option<String>, prev_cursor: Option<String>) -> Self {
+        self.next_cursor = next_cursor;
+        self.prev_cursor = prev_cursor;
+        self
+    }
+
+    /// Record how the total was obtained and whether another page follows
+    ///
+    /// The rows fetched decide `has_more`, since an estimated or skipped
+    /// total cannot.
+    pub fn with_count_mode(mut self, count_mode: CountMode, has_more: bool) -> Self {
+        self.count_mode = count_mode;
+        self.has_more = has_more;
+        self
+    }
+
+    /// Attach full-text snippets, one map per item
+    pub fn with_headlines(mut self, headlines: Vec<HashMap<String, String>>) -> Self {
+        self.headlines = headlines;
+        self
+    }
+
+    /// Attach facet counts, by field
+    pub fn with_facets(mut self, facets: HashMap<String, Vec<FacetCount>>) -> Self {
+        self.facets = facets;
+        self
+    }
+
     /// Check if there are more pages after the current one
     pub fn has_next_page(&self) -> bool {
-        self.page + 1 < self.total_pages
+        self.has_more
     }
 
     /// Check if there are pages before the current one
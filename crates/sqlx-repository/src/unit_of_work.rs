@@ -0,0 +1,265 @@
+//! Unit of work spanning several repositories in one transaction
+//!
+//! A [`UnitOfWork`] owns a database transaction and hands out
+//! transaction-bound views of any derived repository, so writes through
+//! different repositories commit or roll back together.
+//!
+//! Dropping a unit of work without calling [`commit`](UnitOfWork::commit)
+//! rolls it back. [`UnitOfWork::run`] wraps the whole lifecycle: it commits
+//! when the closure returns `Ok` and rolls back when it returns `Err`.
+//! [`UnitOfWork::run_with_retry`] additionally reruns the closure in a fresh
+//! transaction when it fails with a serialization failure or deadlock.
+
+use crate::aggregate::{AggregateQuery, AggregateRow};
+use crate::backends::DatabaseBackend;
+use crate::error::{RepositoryError, RepositoryResult};
+use crate::repository::Repository;
+use crate::retry::RetryPolicy;
+use crate::search::{FacetCount, SearchParams, SearchResult};
+use sqlx::{Acquire, ColumnIndex, Database, Decode, Encode, Executor, FromRow, IntoArguments, Pool, Transaction, Type};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Boxed future returned by [`UnitOfWork::run`] closures
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Repository that can be bound to a [`UnitOfWork`]
+///
+/// Implemented by `#[derive(Repository)]` for every generated repository.
+pub trait TransactionalRepository: Sized + Send + Sync {
+    /// Entity type managed by the repository
+    type Entity;
+    /// Primary key type of the entity
+    type Id;
+    /// Backend whose SQL dialect the repository speaks
+    type Backend: DatabaseBackend;
+
+    /// Build the repository from a connection pool
+    fn from_pool(pool: Pool<<Self::Backend as DatabaseBackend>::Database>) -> Self;
+}
+
+/// A transaction shared by any number of repositories
+pub struct UnitOfWork<'c, DB: Database> {
+    tx: Transaction<'c, DB>,
+    pool: Pool<DB>,
+}
+
+impl<DB: Database> UnitOfWork<'static, DB> {
+    /// Begin a new transaction on `pool`
+    pub async fn begin(pool: &Pool<DB>) -> RepositoryResult<Self> {
+        Ok(Self {
+            tx: pool.begin().await?,
+            pool: pool.clone(),
+        })
+    }
+
+    /// Run `f` in a new unit of work, committing on `Ok` and rolling back on `Err`
+    ///
+    /// ```rust,ignore
+    /// let user = UnitOfWork::run(&pool, |uow| Box::pin(async move {
+    ///     let user = uow.repo::<UserRepository>().create(create_user).await?;
+    ///     uow.repo::<PostRepository>().create(create_post(user.id)).await?;
+    ///     Ok(user)
+    /// })).await?;
+    /// ```
+    pub async fn run<F, T>(pool: &Pool<DB>, f: F) -> RepositoryResult<T>
+    where
+        F: for<'u> FnOnce(&'u mut UnitOfWork<'static, DB>) -> BoxFuture<'u, RepositoryResult<T>>,
+    {
+        let mut uow = Self::begin(pool).await?;
+
+        match f(&mut uow).await {
+            Ok(value) => {
+                uow.commit().await?;
+                Ok(value)
+            }
+            Err(err) => {
+                // The closure's error is the one worth reporting; a failed
+                // rollback still ends the transaction when the connection drops
+                let _ = uow.rollback().await;
+                Err(err)
+            }
+        }
+    }
+
+    /// Like [`run`](Self::run), but retries transient failures according to `policy`
+    ///
+    /// When an attempt fails with a [retryable](RepositoryError::is_retryable)
+    /// error (including at commit), it is rolled back and `f` runs again in a
+    /// new transaction after the policy's backoff. Other errors are returned
+    /// immediately; running out of attempts returns
+    /// [`RepositoryError::RetriesExhausted`] wrapping the last error.
+    ///
+    /// `f` may run several times, so it must not have side effects outside
+    /// the transaction. To run at `SERIALIZABLE`, set the isolation level as
+    /// the first statement inside `f`:
+    ///
+    /// ```rust,ignore
+    /// UnitOfWork::run_with_retry(&pool, &RetryPolicy::default(), |uow| Box::pin(async move {
+    ///     sqlx::query("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+    ///         .execute(uow.connection())
+    ///         .await?;
+    ///     uow.repo::<AccountRepository>().update(id, debit).await
+    /// })).await?;
+    /// ```
+    pub async fn run_with_retry<F, T>(pool: &Pool<DB>, policy: &RetryPolicy, f: F) -> RepositoryResult<T>
+    where
+        F: for<'u> Fn(&'u mut UnitOfWork<'static, DB>) -> BoxFuture<'u, RepositoryResult<T>>,
+    {
+        let max_attempts = policy.max_attempts.max(1);
+        let mut attempt = 1;
+
+        loop {
+            match Self::run(pool, &f).await {
+                Err(err) if err.is_retryable() => {
+                    if attempt >= max_attempts {
+                        return Err(RepositoryError::RetriesExhausted {
+                            attempts: attempt,
+                            source: Box::new(err),
+                        });
+                    }
+                    tokio::time::sleep(policy.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<'c, DB: Database> UnitOfWork<'c, DB> {
+    /// A view of repository `R` whose operations run inside this unit of work
+    pub fn repo<R>(&mut self) -> TxRepository<'_, R>
+    where
+        R: TransactionalRepository,
+        R::Backend: DatabaseBackend<Database = DB>,
+    {
+        TxRepository {
+            repo: R::from_pool(self.pool.clone()),
+            conn: &mut self.tx,
+        }
+    }
+
+    /// Start a nested unit of work backed by a savepoint
+    ///
+    /// Committing the nested unit releases the savepoint; rolling it back or
+    /// dropping it undoes only the work done since the savepoint.
+    pub async fn savepoint(&mut self) -> RepositoryResult<UnitOfWork<'_, DB>> {
+        Ok(UnitOfWork {
+            tx: (&mut self.tx).begin().await?,
+            pool: self.pool.clone(),
+        })
+    }
+
+    /// The underlying connection, for queries outside the repositories
+    pub fn connection(&mut self) -> &mut DB::Connection {
+        &mut self.tx
+    }
+
+    /// Commit all work done in this unit
+    pub async fn commit(self) -> RepositoryResult<()> {
+        self.tx.commit().await?;
+        Ok(())
+    }
+
+    /// Roll back all work done in this unit
+    pub async fn rollback(self) -> RepositoryResult<()> {
+        self.tx.rollback().await?;
+        Ok(())
+    }
+}
+
+/// Repository operations bound to a [`UnitOfWork`]
+///
+/// Obtained from [`UnitOfWork::repo`]. Each method runs the matching
+/// connection-scoped `*_in` operation on the unit of work's transaction.
+pub struct TxRepository<'u, R: TransactionalRepository> {
+    repo: R,
+    conn: &'u mut <<R::Backend as DatabaseBackend>::Database as Database>::Connection,
+}
+
+impl<'u, R> TxRepository<'u, R>
+where
+    R: TransactionalRepository + Repository<R::Entity, R::Id, R::Backend>,
+    R::Entity: for<'r> FromRow<'r, <<R::Backend as DatabaseBackend>::Database as Database>::Row> + Send + Unpin,
+    R::Id: Send
+        + Sync
+        + Clone
+        + 'static
+        + for<'e> Encode<'e, <R::Backend as DatabaseBackend>::Database>
+        + for<'d> Decode<'d, <R::Backend as DatabaseBackend>::Database>
+        + Type<<R::Backend as DatabaseBackend>::Database>,
+    for<'q> <<R::Backend as DatabaseBackend>::Database as Database>::Arguments<'q>:
+        IntoArguments<'q, <R::Backend as DatabaseBackend>::Database>,
+    for<'c> &'c mut <<R::Backend as DatabaseBackend>::Database as Database>::Connection:
+        Executor<'c, Database = <R::Backend as DatabaseBackend>::Database>,
+    String: for<'e> Encode<'e, <R::Backend as DatabaseBackend>::Database> + Type<<R::Backend as DatabaseBackend>::Database>,
+    i64: for<'d> Decode<'d, <R::Backend as DatabaseBackend>::Database> + Type<<R::Backend as DatabaseBackend>::Database>,
+    usize: ColumnIndex<<<R::Backend as DatabaseBackend>::Database as Database>::Row>,
+{
+    /// Create a new entity
+    pub async fn create(&mut self, data: R::CreateType) -> RepositoryResult<R::Entity> {
+        self.repo.create_in(self.conn, data).await
+    }
+
+    /// Update an existing entity by ID
+    pub async fn update(&mut self, id: R::Id, data: R::UpdateType) -> RepositoryResult<Option<R::Entity>> {
+        self.repo.update_in(self.conn, id, data).await
+    }
+
+    /// Find an entity by its ID
+    pub async fn find_by_id(&mut self, id: R::Id) -> RepositoryResult<Option<R::Entity>> {
+        self.repo.find_by_id_in(self.conn, id).await
+    }
+
+    /// Find all entities
+    pub async fn find_all(&mut self) -> RepositoryResult<Vec<R::Entity>> {
+        self.repo.find_all_in(self.conn).await
+    }
+
+    /// Delete an entity by ID (soft delete if enabled, otherwise hard delete)
+    pub async fn delete(&mut self, id: R::Id) -> RepositoryResult<bool> {
+        self.repo.delete_in(self.conn, id).await
+    }
+
+    /// Permanently delete an entity by ID
+    pub async fn hard_delete(&mut self, id: R::Id) -> RepositoryResult<bool> {
+        self.repo.hard_delete_in(self.conn, id).await
+    }
+
+    /// Restore a soft-deleted entity by ID
+    pub async fn restore(&mut self, id: R::Id) -> RepositoryResult<Option<R::Entity>> {
+        self.repo.restore_in(self.conn, id).await
+    }
+
+    /// Search entities with filtering, pagination, and sorting
+    pub async fn search(&mut self, params: SearchParams) -> RepositoryResult<SearchResult<R::Entity>> {
+        self.repo.search_in(self.conn, params).await
+    }
+
+    /// Count entities matching the given search parameters
+    pub async fn count(&mut self, params: SearchParams) -> RepositoryResult<i64> {
+        self.repo.count_in(self.conn, params).await
+    }
+
+    /// Compute aggregates over the entities matching the given search parameters
+    pub async fn aggregate(&mut self, params: SearchParams, query: AggregateQuery) -> RepositoryResult<Vec<AggregateRow>> {
+        self.repo.aggregate_in(self.conn, params, query).await
+    }
+
+    /// Distinct values of `field` among the entities matching the given search parameters, with their counts
+    pub async fn distinct_values(&mut self, field: &str, params: SearchParams) -> RepositoryResult<Vec<FacetCount>> {
+        self.repo.distinct_values_in(self.conn, field, params).await
+    }
+
+    /// Up to `limit` distinct values of the text `field` starting with `prefix`, among the entities matching `params`
+    pub async fn suggest(&mut self, field: &str, prefix: &str, limit: u32, params: SearchParams) -> RepositoryResult<Vec<String>> {
+        self.repo.suggest_in(self.conn, field, prefix, limit, params).await
+    }
+
+    /// Count entities matching the given search parameters by each of `params.facets`
+    pub async fn facets(&mut self, params: SearchParams) -> RepositoryResult<HashMap<String, Vec<FacetCount>>> {
+        self.repo.facets_in(self.conn, params).await
+    }
+}
@@ -31,7 +31,8 @@ pub struct User {
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
 #[repository(table = "posts")]
 #[repository(searchable_fields(title, content))]
-#[repository(filterable_fields(published, user_id))]
+#[repository(filterable_fields(published, user_id, created_at))]
+#[repository(sortable_fields(title, created_at))]
 pub struct Post {
     pub id: i32,
     pub title: String,
@@ -42,6 +43,21 @@ pub struct Post {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Posts searched with PostgreSQL full-text search
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "posts")]
+#[repository(full_text(config = "english", fields(title, content)))]
+#[repository(filterable_fields(user_id))]
+pub struct Article {
+    pub id: i32,
+    pub title: String,
+    pub content: String,
+    pub user_id: i32,
+    pub published: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 
 #[tokio::test]
 async fn test_user_crud_operations() {
@@ -215,6 +231,857 @@ async fn test_search_and_filtering() {
     cleanup_test_data(repo.pool()).await;
 }
 
+#[tokio::test]
+async fn test_filter_operators() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool);
+    
+    // Each user gets a department unique to this run so counts are exact
+    let suffix = get_unique_suffix();
+    let mut created_users = Vec::new();
+    for (name, status, department) in [
+        ("Alice", "active", format!("eng_{}", suffix)),
+        ("Bob", "pending", format!("sales_{}", suffix)),
+        ("Carol", "inactive", format!("eng_ops_{}", suffix)),
+    ] {
+        let user = user_repo.create(CreateUser {
+            name: format!("{} {}", name, suffix),
+            email: format!("{}.ops{}@example.com", name.to_lowercase(), suffix),
+            status: status.to_string(),
+            department,
+        }).await.expect("Failed to create test user");
+        created_users.push(user);
+    }
+    let departments: Vec<String> = created_users.iter().map(|u| u.department.clone()).collect();
+    
+    let count_where = |mut conditions: Vec<Filter>| {
+        let user_repo = &user_repo;
+        conditions.push(Filter::is_in("department", departments.clone()));
+        async move {
+            user_repo.count(SearchParams {
+                conditions,
+                ..Default::default()
+            }).await.expect("Failed to count users")
+        }
+    };
+    
+    assert_eq!(count_where(vec![]).await, 3);
+    assert_eq!(count_where(vec![Filter::ne("status", "active")]).await, 2);
+    assert_eq!(count_where(vec![Filter::is_in("status", ["active", "pending"])]).await, 2);
+    assert_eq!(count_where(vec![Filter::not_in("status", ["active", "pending"])]).await, 1);
+    assert_eq!(count_where(vec![Filter::is_in("status", Vec::<String>::new())]).await, 0);
+    assert_eq!(count_where(vec![Filter::starts_with("department", "eng_")]).await, 2);
+    assert_eq!(count_where(vec![Filter::contains("department", "_ops_")]).await, 1);
+    assert_eq!(count_where(vec![Filter::gt("status", "active"), Filter::lte("status", "pending")]).await, 2);
+    assert_eq!(count_where(vec![Filter::between("status", "inactive", "pending")]).await, 2);
+    
+    // Operators deserialize from JSON request bodies
+    let params: SearchParams = serde_json::from_value(serde_json::json!({
+        "filters": {},
+        "page": 0,
+        "per_page": 10,
+        "sort_by": null,
+        "sort_order": "asc",
+        "scope": "active",
+        "conditions": [
+            {"field": "department", "op": "in", "value": departments},
+            {"field": "status", "op": "ne", "value": "pending"}
+        ]
+    })).expect("Failed to deserialize search params");
+    let results = user_repo.search(params).await.expect("Failed to filter users");
+    assert_eq!(results.total_count, 2);
+    assert!(results.items.iter().all(|u| u.status != "pending"));
+    
+    // Typed values bind as booleans and integers
+    let alice = &created_users[0];
+    for published in [true, false] {
+        post_repo.create(CreatePost {
+            title: format!("Operator Post {}", suffix),
+            content: "content".to_string(),
+            user_id: alice.id,
+            published,
+        }).await.expect("Failed to create post");
+    }
+    let posts = post_repo.search(SearchParams {
+        conditions: vec![
+            Filter::eq("user_id", alice.id),
+            Filter::eq("published", true),
+        ],
+        ..Default::default()
+    }).await.expect("Failed to filter posts");
+    assert_eq!(posts.total_count, 1);
+    assert!(posts.items[0].published);
+    
+    // Clean up
+    for post in post_repo.search(SearchParams {
+        conditions: vec![Filter::eq("user_id", alice.id)],
+        ..Default::default()
+    }).await.expect("Failed to list posts").items {
+        post_repo.hard_delete(post.id).await.expect("Failed to delete test post");
+    }
+    for user in created_users {
+        user_repo.hard_delete(user.id).await.expect("Failed to delete test user");
+    }
+}
+
+#[tokio::test]
+async fn test_filter_expression_trees() {
+    let pool = setup_test_db().await;
+    let repo = UserRepository::new(pool);
+    
+    let suffix = get_unique_suffix();
+    let department = |name: &str| format!("{}_{}", name, suffix);
+    let mut created_users = Vec::new();
+    for (name, status, dept) in [
+        ("Ann", "active", department("eng")),
+        ("Ben", "pending", department("eng")),
+        ("Cat", "active", department("sales")),
+        ("Dan", "inactive", department("eng")),
+    ] {
+        let user = repo.create(CreateUser {
+            name: format!("{} {}", name, suffix),
+            email: format!("{}.tree{}@example.com", name.to_lowercase(), suffix),
+            status: status.to_string(),
+            department: dept,
+        }).await.expect("Failed to create test user");
+        created_users.push(user);
+    }
+    let this_run = Filter::is_in("department", [department("eng"), department("sales")]);
+    
+    // (status = active OR status = pending) AND NOT department = sales
+    let results = repo.search(SearchParams {
+        filter: Some(FilterExpr::and([
+            FilterExpr::from(this_run.clone()),
+            FilterExpr::or([Filter::eq("status", "active"), Filter::eq("status", "pending")]),
+            FilterExpr::not(Filter::eq("department", department("sales"))),
+        ])),
+        sort_by: Some("name".to_string()),
+        ..Default::default()
+    }).await.expect("Failed to search with filter tree");
+    let names: Vec<&str> = results.items.iter().map(|u| u.name.split(' ').next().unwrap()).collect();
+    assert_eq!(names, ["Ann", "Ben"]);
+    
+    // Trees combine with flat conditions, and empty groups are constant
+    let count = repo.count(SearchParams {
+        conditions: vec![this_run.clone()],
+        filter: Some(FilterExpr::or(Vec::<FilterExpr>::new())),
+        ..Default::default()
+    }).await.expect("Failed to count with empty group");
+    assert_eq!(count, 0);
+    let count = repo.count(SearchParams {
+        conditions: vec![this_run.clone()],
+        filter: Some(FilterExpr::not(FilterExpr::and(Vec::<FilterExpr>::new()))),
+        ..Default::default()
+    }).await.expect("Failed to count with negated empty group");
+    assert_eq!(count, 0);
+    
+    // Fields outside filterable_fields are rejected
+    let err = repo.search(SearchParams {
+        filter: Some(FilterExpr::or([Filter::eq("status", "active"), Filter::eq("email", "x@example.com")])),
+        ..Default::default()
+    }).await.expect_err("Non-filterable field should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    
+    for user in created_users {
+        repo.hard_delete(user.id).await.expect("Failed to delete test user");
+    }
+}
+
+#[tokio::test]
+async fn test_filters_bind_column_types() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool);
+    
+    let suffix = get_unique_suffix();
+    let user = user_repo.create(CreateUser {
+        name: format!("Typed Filter User {}", suffix),
+        email: format!("typed{}@example.com", suffix),
+        status: "active".to_string(),
+        department: "test".to_string(),
+    }).await.expect("Failed to create user");
+    
+    let post = post_repo.create(CreatePost {
+        title: format!("Typed Post {}", suffix),
+        content: "content".to_string(),
+        user_id: user.id,
+        published: true,
+    }).await.expect("Failed to create post");
+    
+    // String filters from query strings are parsed as each column's type
+    let mut filters = std::collections::HashMap::new();
+    filters.insert("user_id".to_string(), user.id.to_string());
+    filters.insert("published".to_string(), "true".to_string());
+    let count = post_repo.count(SearchParams {
+        filters: filters.clone(),
+        ..Default::default()
+    }).await.expect("Failed to filter on integer and boolean columns");
+    assert_eq!(count, 1);
+    
+    // Timestamps compare as timestamps
+    let created_after = (post.created_at - chrono::Duration::minutes(1)).to_rfc3339();
+    let count = post_repo.count(SearchParams {
+        filters: filters.clone(),
+        conditions: vec![Filter::gt("created_at", created_after)],
+        ..Default::default()
+    }).await.expect("Failed to filter on timestamp column");
+    assert_eq!(count, 1);
+    let count = post_repo.count(SearchParams {
+        filters: filters.clone(),
+        conditions: vec![Filter::gt("created_at", (post.created_at + chrono::Duration::minutes(1)).to_rfc3339())],
+        ..Default::default()
+    }).await.expect("Failed to filter on timestamp column");
+    assert_eq!(count, 0);
+    
+    // Numeric and timestamp fields take inclusive range bounds, including relative times
+    let count_range = |bounds: &[(&str, String)]| {
+        let mut filters = filters.clone();
+        filters.extend(bounds.iter().map(|(key, value)| (key.to_string(), value.clone())));
+        let post_repo = &post_repo;
+        async move {
+            post_repo.count(SearchParams {
+                filters,
+                ..Default::default()
+            }).await
+        }
+    };
+    let recent = [("created_at_from", "now-1h".to_string()), ("created_at_to", "now+1h".to_string())];
+    assert_eq!(count_range(&recent).await.unwrap(), 1);
+    assert_eq!(count_range(&[("created_at_gte", "now+1h".to_string())]).await.unwrap(), 0);
+    assert_eq!(count_range(&[("created_at_lte", "now-1d".to_string())]).await.unwrap(), 0);
+    let own_id = [("user_id_from", user.id.to_string()), ("user_id_to", user.id.to_string())];
+    assert_eq!(count_range(&own_id).await.unwrap(), 1);
+    assert_eq!(count_range(&[("user_id_gte", (user.id + 1).to_string())]).await.unwrap(), 0);
+    let err = count_range(&[("created_at_from", "now-7x".to_string())]).await.expect_err("Bad relative time should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    // Only numeric and date/time fields have ranges
+    let err = count_range(&[("published_from", "true".to_string())]).await.expect_err("Boolean range should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    
+    // Values that don't parse are rejected before reaching the database
+    filters.insert("published".to_string(), "maybe".to_string());
+    let err = post_repo.count(SearchParams {
+        filters,
+        ..Default::default()
+    }).await.expect_err("Unparseable boolean should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    
+    let err = post_repo.search(SearchParams {
+        conditions: vec![Filter::gt("user_id", "abc")],
+        ..Default::default()
+    }).await.expect_err("Unparseable integer should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    
+    // Clean up
+    post_repo.hard_delete(post.id).await.expect("Failed to delete test post");
+    user_repo.hard_delete(user.id).await.expect("Failed to delete test user");
+}
+
+#[tokio::test]
+async fn test_cursor_pagination() {
+    let pool = setup_test_db().await;
+    let repo = UserRepository::new(pool);
+    
+    let suffix = get_unique_suffix();
+    let department = format!("cursor_{}", suffix);
+    let mut created_users = Vec::new();
+    for (i, status) in ["active", "pending", "active", "inactive", "active"].iter().enumerate() {
+        let user = repo.create(CreateUser {
+            name: format!("Cursor User {} {}", i, suffix),
+            email: format!("cursor{}.{}@example.com", i, suffix),
+            status: status.to_string(),
+            department: department.clone(),
+        }).await.expect("Failed to create test user");
+        created_users.push(user);
+    }
+    
+    let params = |cursor: Option<String>| {
+        let mut filters = std::collections::HashMap::new();
+        filters.insert("department".to_string(), department.clone());
+        SearchParams {
+            filters,
+            cursor,
+            per_page: 2,
+            sort_by: Some("status".to_string()),
+            sort_order: SortOrder::Desc,
+            ..Default::default()
+        }
+    };
+    
+    // Walk forwards: pending, inactive, then the actives by descending id
+    let first = repo.search(params(None)).await.expect("Failed to fetch first page");
+    assert!(first.prev_cursor.is_none());
+    let second = repo.search(params(first.next_cursor.clone())).await.expect("Failed to fetch second page");
+    let third = repo.search(params(second.next_cursor.clone())).await.expect("Failed to fetch third page");
+    assert!(third.next_cursor.is_none());
+    
+    let ids = |page: &SearchResult<User>| page.items.iter().map(|u| u.id).collect::<Vec<_>>();
+    let user_id = |i: usize| created_users[i].id;
+    assert_eq!(ids(&first), [user_id(1), user_id(3)]);
+    assert_eq!(ids(&second), [user_id(4), user_id(2)]);
+    assert_eq!(ids(&third), [user_id(0)]);
+    assert_eq!(third.total_count, 5);
+    
+    // Offset pages count alongside their rows; an empty page counts separately
+    assert_eq!(first.total_count, 5);
+    let past_end = repo.search(SearchParams { page: 3, ..params(None) }).await.expect("Failed to fetch page past the end");
+    assert!(past_end.items.is_empty());
+    assert_eq!(past_end.total_count, 5);
+    
+    // Skipped and estimated totals; the last page still knows the exact total
+    let uncounted = repo.search(SearchParams { count_mode: CountMode::None, ..params(None) }).await.expect("Failed to skip count");
+    assert_eq!(uncounted.count_mode, CountMode::None);
+    assert_eq!(uncounted.total_count, 2);
+    assert!(uncounted.has_next_page());
+    let estimated = repo.search(SearchParams { count_mode: CountMode::Estimated, ..params(None) }).await.expect("Failed to estimate count");
+    assert_eq!(estimated.count_mode, CountMode::Estimated);
+    assert!(estimated.total_count >= 2);
+    assert_eq!(ids(&estimated), ids(&first));
+    let last = repo.search(SearchParams { count_mode: CountMode::None, page: 2, ..params(None) }).await.expect("Failed to fetch last page");
+    assert_eq!((last.total_count, last.count_mode), (5, CountMode::Exact));
+    assert!(!last.has_next_page());
+    
+    // Walk back from the last page
+    let back = repo.search(params(third.prev_cursor.clone())).await.expect("Failed to page backwards");
+    assert_eq!(ids(&back), ids(&second));
+    let back = repo.search(params(back.prev_cursor.clone())).await.expect("Failed to page backwards");
+    assert_eq!(ids(&back), ids(&first));
+    assert!(back.prev_cursor.is_none());
+    
+    // Tampered cursors are rejected
+    let cursor = first.next_cursor.clone().unwrap();
+    let (payload, tag) = cursor.split_once('.').unwrap();
+    let tampered = format!("{}.{}", payload, tag.chars().rev().collect::<String>());
+    for bad in [tampered, "not-a-cursor".to_string()] {
+        let err = repo.search(params(Some(bad))).await.expect_err("Tampered cursor should be rejected");
+        assert!(matches!(err, RepositoryError::Validation(_)));
+    }
+    
+    // A cursor only continues the sort it was issued for
+    let err = repo.search(SearchParams {
+        sort_order: SortOrder::Asc,
+        ..params(Some(cursor))
+    }).await.expect_err("Cursor with another sort order should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    
+    for user in created_users {
+        repo.hard_delete(user.id).await.expect("Failed to delete test user");
+    }
+}
+
+#[tokio::test]
+async fn test_sort_by_is_validated() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool);
+    
+    let sort_by = |field: &str| SearchParams {
+        sort_by: Some(field.to_string()),
+        per_page: 1,
+        ..Default::default()
+    };
+    
+    // Without sortable_fields any entity field is allowed
+    user_repo.search(sort_by("email")).await.expect("Failed to sort by a field");
+    
+    // With sortable_fields only the listed fields and the id are
+    post_repo.search(sort_by("title")).await.expect("Failed to sort by a sortable field");
+    post_repo.search(sort_by("id")).await.expect("Failed to sort by id");
+    
+    for field in ["content", "name; DROP TABLE users; --", "missing"] {
+        let err = post_repo.search(sort_by(field)).await.expect_err("Unsortable field should be rejected");
+        assert!(matches!(err, RepositoryError::Validation(_)));
+    }
+    let err = user_repo.search(sort_by("1; DROP TABLE users")).await.expect_err("Unknown field should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+
+    // Every unknown sort key is reported along with the allowed fields
+    let err = post_repo
+        .search(SearchParams {
+            sort: SortKey::parse_list("-content,title,missing").unwrap(),
+            ..Default::default()
+        })
+        .await
+        .expect_err("Unsortable fields should be rejected");
+    assert_eq!(
+        err.to_string(),
+        "Validation error: Unknown sort fields 'content', 'missing' (allowed: 'id', 'title', 'created_at')"
+    );
+}
+
+#[tokio::test]
+async fn test_multi_column_sort() {
+    let pool = setup_test_db().await;
+    let repo = UserRepository::new(pool);
+    
+    let suffix = get_unique_suffix();
+    let department = |name: &str| format!("{}_sort_{}", name, suffix);
+    let mut created_users = Vec::new();
+    for (name, dept, deleted) in [
+        ("Ann", "sales", false),
+        ("Ben", "eng", true),
+        ("Cat", "eng", false),
+        ("Dan", "sales", true),
+        ("Eve", "eng", false),
+        ("Fay", "eng", true),
+    ] {
+        let user = repo.create(CreateUser {
+            name: format!("{} {}", name, suffix),
+            email: format!("{}.sort{}@example.com", name.to_lowercase(), suffix),
+            status: "active".to_string(),
+            department: department(dept),
+        }).await.expect("Failed to create test user");
+        if deleted {
+            repo.delete(user.id).await.expect("Failed to delete test user");
+        }
+        created_users.push(user);
+    }
+    
+    let params = |cursor: Option<String>, per_page: u32| SearchParams {
+        conditions: vec![Filter::is_in("department", [department("eng"), department("sales")])],
+        sort: SortKey::parse_list("department,deleted_at:nulls_first,name").unwrap(),
+        scope: RecordScope::All,
+        cursor,
+        per_page,
+        ..Default::default()
+    };
+    
+    // Department, then live users before deleted ones (PostgreSQL would put
+    // NULLs last in ascending order), then name
+    let results = repo.search(params(None, 10)).await.expect("Failed to sort by several columns");
+    let names: Vec<&str> = results.items.iter().map(|u| u.name.split(' ').next().unwrap()).collect();
+    assert_eq!(names, ["Cat", "Eve", "Ben", "Fay", "Ann", "Dan"]);
+    
+    // Cursors follow the same order
+    let mut ids = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = repo.search(params(cursor, 2)).await.expect("Failed to fetch page");
+        ids.extend(page.items.iter().map(|u| u.id));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    assert_eq!(ids, results.items.iter().map(|u| u.id).collect::<Vec<_>>());
+    
+    // Every key is checked against the entity's fields
+    let err = repo.search(SearchParams {
+        sort: SortKey::parse_list("department,password").unwrap(),
+        ..Default::default()
+    }).await.expect_err("Unknown sort key should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    
+    for user in created_users {
+        repo.hard_delete(user.id).await.expect("Failed to delete test user");
+    }
+}
+
+#[tokio::test]
+async fn test_text_search_match_modes() {
+    let pool = setup_test_db().await;
+    let repo = UserRepository::new(pool);
+    
+    let suffix = get_unique_suffix();
+    let department = format!("match_{}", suffix);
+    let mut created_users = Vec::new();
+    for name in ["50% Off", "500 Club", "a_b Test", "aXb Test", "Test ANN"] {
+        let user = repo.create(CreateUser {
+            name: name.to_string(),
+            email: format!("{}.match{}@example.com", name.replace(['%', ' '], ""), suffix).to_lowercase(),
+            status: "active".to_string(),
+            department: department.clone(),
+        }).await.expect("Failed to create test user");
+        created_users.push(user);
+    }
+    
+    let search = |query: &str, match_mode: MatchMode| {
+        let repo = &repo;
+        let department = department.clone();
+        let query = query.to_string();
+        async move {
+            let mut filters = std::collections::HashMap::new();
+            filters.insert("department".to_string(), department);
+            let results = repo.search(SearchParams {
+                query: Some(query),
+                match_mode,
+                filters,
+                sort_by: Some("name".to_string()),
+                ..Default::default()
+            }).await.expect("Failed to search");
+            results.items.into_iter().map(|u| u.name).collect::<Vec<_>>()
+        }
+    };
+    
+    // Wildcards in the query match literally
+    assert_eq!(search("50%", MatchMode::Contains).await, ["50% Off"]);
+    assert_eq!(search("a_b", MatchMode::Contains).await, ["a_b Test"]);
+    
+    assert_eq!(search("A_B", MatchMode::Prefix).await, ["a_b Test"]);
+    assert_eq!(search("test", MatchMode::Prefix).await, ["Test ANN"]);
+    let mut names = search("test", MatchMode::Suffix).await;
+    names.sort();
+    assert_eq!(names, ["aXb Test", "a_b Test"]);
+    assert_eq!(search("500 club", MatchMode::Exact).await, ["500 Club"]);
+    assert!(search("500", MatchMode::Exact).await.is_empty());
+    assert_eq!(search("ANN", MatchMode::CaseSensitive).await, ["Test ANN"]);
+    assert!(search("Ann", MatchMode::CaseSensitive).await.is_empty());
+    
+    for user in created_users {
+        repo.hard_delete(user.id).await.expect("Failed to delete test user");
+    }
+}
+
+#[tokio::test]
+async fn test_full_text_search() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let article_repo = ArticleRepository::new(pool);
+    
+    let suffix = get_unique_suffix();
+    let user = user_repo.create(CreateUser {
+        name: format!("Full Text User {}", suffix),
+        email: format!("fulltext{}@example.com", suffix),
+        status: "active".to_string(),
+        department: "test".to_string(),
+    }).await.expect("Failed to create user");
+    
+    let mut articles = Vec::new();
+    for (title, content) in [
+        ("Running databases", "Notes on running a database in production."),
+        ("Cooking", "A recipe that mentions databases once."),
+        ("Database indexes", "Indexes make database queries fast; every database needs them."),
+        ("Gardening", "Nothing relevant here."),
+    ] {
+        let article = article_repo.create(CreateArticle {
+            title: title.to_string(),
+            content: content.to_string(),
+            user_id: user.id,
+            published: true,
+        }).await.expect("Failed to create article");
+        articles.push(article);
+    }
+    
+    let mut filters = std::collections::HashMap::new();
+    filters.insert("user_id".to_string(), user.id.to_string());
+    let params = |query: &str| SearchParams {
+        query: Some(query.to_string()),
+        filters: filters.clone(),
+        ..Default::default()
+    };
+    
+    // Stemming matches "databases" to "database"; web-search syntax excludes terms
+    let results = article_repo.search(params("database")).await.expect("Failed full-text search");
+    assert_eq!(results.total_count, 3);
+    assert!(results.headlines.is_empty());
+    let results = article_repo.search(params("database -recipe")).await.expect("Failed full-text search");
+    assert_eq!(results.total_count, 2);
+    assert_eq!(article_repo.count(params("\"production database\"")).await.unwrap(), 0);
+    
+    // Ranked by relevance, with highlighted snippets per field
+    let results = article_repo.search(SearchParams {
+        order_by_rank: true,
+        headlines: true,
+        ..params("database")
+    }).await.expect("Failed ranked full-text search");
+    assert_eq!(results.items[0].title, "Database indexes");
+    assert_eq!(results.headlines.len(), results.items.len());
+    assert!(results.headlines[0]["title"].contains("<b>Database</b>"));
+    assert!(results.headlines[0]["content"].contains("<b>database</b>"));
+    assert!(results.next_cursor.is_none());
+    
+    let err = article_repo.search(SearchParams {
+        order_by_rank: true,
+        cursor: Some("anything".to_string()),
+        ..params("database")
+    }).await.expect_err("Ranking with a cursor should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    
+    // Repositories without full_text reject ranking
+    let err = user_repo.search(SearchParams {
+        order_by_rank: true,
+        ..Default::default()
+    }).await.expect_err("Ranking without full_text should be rejected");
+    assert!(matches!(err, RepositoryError::Configuration(_)));
+    
+    for article in articles {
+        article_repo.hard_delete(article.id).await.expect("Failed to delete article");
+    }
+    user_repo.hard_delete(user.id).await.expect("Failed to delete user");
+}
+
+#[tokio::test]
+async fn test_facet_counts() {
+    let pool = setup_test_db().await;
+    let repo = UserRepository::new(pool);
+    
+    let suffix = get_unique_suffix();
+    let mut users = Vec::new();
+    for (i, (status, department)) in [
+        ("active", "sales"),
+        ("active", "sales"),
+        ("active", "support"),
+        ("inactive", "sales"),
+        ("pending", "support"),
+    ].iter().enumerate() {
+        let user = repo.create(CreateUser {
+            name: format!("Facet User {} {}", i, suffix),
+            email: format!("facet{}.{}@example.com", i, suffix),
+            status: status.to_string(),
+            department: format!("{}_{}", department, suffix),
+        }).await.expect("Failed to create test user");
+        users.push(user);
+    }
+    
+    let counts = |facets: &std::collections::HashMap<String, Vec<FacetCount>>, field: &str| {
+        facets[field].iter().map(|facet| (facet.value.as_ref().unwrap().to_string(), facet.count)).collect::<Vec<_>>()
+    };
+    let sales = format!("sales_{}", suffix);
+    let support = format!("support_{}", suffix);
+    
+    // Counted over the text query, most common first
+    let params = SearchParams {
+        query: Some("Facet User".to_string()),
+        facets: vec!["status".to_string(), "department".to_string()],
+        ..Default::default()
+    };
+    let results = repo.search(params.clone()).await.expect("Failed to search with facets");
+    assert_eq!(counts(&results.facets, "status"), [("active".to_string(), 3), ("inactive".to_string(), 1), ("pending".to_string(), 1)]);
+    assert_eq!(counts(&results.facets, "department"), [(sales.clone(), 3), (support.clone(), 2)]);
+    
+    // Selecting a status narrows the other facets but still lists every status
+    let mut filters = std::collections::HashMap::new();
+    filters.insert("status".to_string(), "active".to_string());
+    let facets = repo.facets(SearchParams { filters, ..params.clone() }).await.expect("Failed to count facets");
+    assert_eq!(counts(&facets, "status"), [("active".to_string(), 3), ("inactive".to_string(), 1), ("pending".to_string(), 1)]);
+    assert_eq!(counts(&facets, "department"), [(sales, 2), (support, 1)]);
+    
+    let err = repo.facets(SearchParams {
+        facets: vec!["email".to_string()],
+        ..Default::default()
+    }).await.expect_err("Unfilterable facet should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    
+    for user in users {
+        repo.hard_delete(user.id).await.expect("Failed to delete test user");
+    }
+}
+
+#[tokio::test]
+async fn test_aggregates() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool);
+    
+    let suffix = get_unique_suffix();
+    let user = user_repo.create(CreateUser {
+        name: format!("Aggregate User {}", suffix),
+        email: format!("aggregate{}@example.com", suffix),
+        status: "active".to_string(),
+        department: "test".to_string(),
+    }).await.expect("Failed to create user");
+    let mut posts = Vec::new();
+    for (i, published) in [true, true, false].into_iter().enumerate() {
+        let post = post_repo.create(CreatePost {
+            title: format!("Aggregate Post {}", i),
+            content: "content".to_string(),
+            user_id: user.id,
+            published,
+        }).await.expect("Failed to create post");
+        posts.push(post);
+    }
+    
+    let mut filters = std::collections::HashMap::new();
+    filters.insert("user_id".to_string(), user.id.to_string());
+    let params = SearchParams { filters, ..Default::default() };
+    
+    // Grouped, typed results
+    let rows = post_repo.aggregate(
+        params.clone(),
+        AggregateQuery::new([
+            Aggregate::count(),
+            Aggregate::sum("user_id"),
+            Aggregate::avg("user_id").alias("mean_user"),
+            Aggregate::max("created_at"),
+        ]).group_by(["published"]),
+    ).await.expect("Failed to aggregate posts");
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].group["published"], Some(FilterValue::Bool(false)));
+    assert_eq!(rows[0].get_i64("count"), Some(1));
+    assert_eq!(rows[1].group["published"], Some(FilterValue::Bool(true)));
+    assert_eq!(rows[1].get_i64("count"), Some(2));
+    assert_eq!(rows[1].get_i64("sum_user_id"), Some(2 * user.id as i64));
+    assert_eq!(rows[1].get_f64("mean_user"), Some(user.id as f64));
+    assert!(matches!(rows[1].values["max_created_at"], Some(FilterValue::Text(_))));
+    
+    // Without a group, one row over every match
+    let rows = post_repo.aggregate(params.clone(), AggregateQuery::new([Aggregate::count(), Aggregate::min("id")]))
+        .await.expect("Failed to aggregate posts");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get_i64("count"), Some(3));
+    assert_eq!(rows[0].get_i64("min_id"), Some(posts[0].id as i64));
+    
+    // Only numeric and date fields aggregate, only count may leave out the
+    // field, only filterable fields group, and names must be unique
+    let sum_rows: Aggregate = serde_json::from_str(r#"{"func": "sum"}"#).unwrap();
+    for query in [
+        AggregateQuery::new([sum_rows]),
+        AggregateQuery::new([Aggregate::sum("title")]),
+        AggregateQuery::new([Aggregate::avg("created_at")]),
+        AggregateQuery::new([Aggregate::count()]).group_by(["title"]),
+        AggregateQuery::new([]),
+        AggregateQuery::new([Aggregate::count(), Aggregate::count()]),
+    ] {
+        let err = post_repo.aggregate(params.clone(), query).await.expect_err("Invalid aggregate should be rejected");
+        assert!(matches!(err, RepositoryError::Validation(_)));
+    }
+    
+    for post in posts {
+        post_repo.hard_delete(post.id).await.expect("Failed to delete post");
+    }
+    user_repo.hard_delete(user.id).await.expect("Failed to delete user");
+}
+
+#[tokio::test]
+async fn test_distinct_values_and_suggest() {
+    let pool = setup_test_db().await;
+    let repo = UserRepository::new(pool);
+    
+    let suffix = get_unique_suffix();
+    let department = format!("zz_distinct_{}", suffix);
+    let mut users = Vec::new();
+    for (i, (name, status)) in [
+        ("Qwertyuiop Jansen", "active"),
+        ("Qwertyuiop Jansen", "active"),
+        ("Qwertyuiop Jakobs", "pending"),
+        ("Qwertyuiop Mills", "pending"),
+        ("Qwertyuiop Jamal", "inactive"),
+    ].iter().enumerate() {
+        let user = repo.create(CreateUser {
+            name: name.to_string(),
+            email: format!("distinct{}.{}@example.com", i, suffix),
+            status: status.to_string(),
+            department: department.clone(),
+        }).await.expect("Failed to create test user");
+        users.push(user);
+    }
+    
+    let mut filters = std::collections::HashMap::new();
+    filters.insert("department".to_string(), department.clone());
+    let params = SearchParams { filters, per_page: 2, ..Default::default() };
+    let values = |counts: Vec<FacetCount>| {
+        counts.into_iter().map(|c| (c.value.unwrap().to_string(), c.count)).collect::<Vec<_>>()
+    };
+    
+    // By frequency, then alphabetically, a page at a time
+    let by_frequency = repo.distinct_values("status", params.clone()).await.expect("Failed to list values");
+    assert_eq!(values(by_frequency), [("active".to_string(), 2), ("pending".to_string(), 2)]);
+    let next = repo.distinct_values("status", SearchParams { page: 1, ..params.clone() }).await.expect("Failed to list values");
+    assert_eq!(values(next), [("inactive".to_string(), 1)]);
+    let alphabetical = repo.distinct_values("status", SearchParams {
+        value_order: ValueOrder::Alphabetical,
+        per_page: 10,
+        ..params.clone()
+    }).await.expect("Failed to list values");
+    assert_eq!(values(alphabetical), [("active".to_string(), 2), ("inactive".to_string(), 1), ("pending".to_string(), 2)]);
+    
+    // Soft-deleted users only show up in their scope
+    repo.delete(users[4].id).await.expect("Failed to soft delete user");
+    let active = repo.distinct_values("status", SearchParams { per_page: 10, ..params.clone() }).await.unwrap();
+    assert_eq!(active.len(), 2);
+    let deleted = repo.distinct_values("status", SearchParams { scope: RecordScope::Deleted, ..params.clone() }).await.unwrap();
+    assert_eq!(values(deleted), [("inactive".to_string(), 1)]);
+    
+    // Suggestions match the prefix regardless of case, most common first
+    let suggestions = repo.suggest("name", "qwertyuiop ja", 5, params.clone()).await.expect("Failed to suggest names");
+    assert_eq!(suggestions, ["Qwertyuiop Jansen", "Qwertyuiop Jakobs"]);
+    assert_eq!(repo.suggest("name", "Qwertyuiop", 1, params.clone()).await.unwrap(), ["Qwertyuiop Jansen"]);
+    assert!(repo.suggest("name", "Qwertyuiop%", 5, params.clone()).await.unwrap().is_empty());
+    
+    // Suggestions follow the scope and filters of the params
+    let deleted = repo.suggest("name", "qwertyuiop", 5, SearchParams { scope: RecordScope::Deleted, ..params.clone() }).await.unwrap();
+    assert_eq!(deleted, ["Qwertyuiop Jamal"]);
+    let pending = repo.suggest("name", "qwertyuiop", 5, SearchParams {
+        conditions: vec![Filter::eq("status", "pending")],
+        ..params.clone()
+    }).await.unwrap();
+    assert_eq!(pending, ["Qwertyuiop Jakobs", "Qwertyuiop Mills"]);
+    
+    // Only searchable or filterable fields, and only text ones for suggestions
+    let err = repo.distinct_values("created_at", SearchParams::default()).await.expect_err("Undeclared field should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    let err = repo.suggest("id", "1", 5, SearchParams::default()).await.expect_err("Undeclared field should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    
+    for user in users {
+        repo.hard_delete(user.id).await.expect("Failed to delete test user");
+    }
+}
+
+#[tokio::test]
+async fn test_fuzzy_search() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepository::new(pool);
+    user_repo.check_fuzzy_search().await.expect("pg_trgm should be installed");
+    
+    let suffix = get_unique_suffix();
+    let department = format!("fuzzy{}", suffix);
+    let mut users = Vec::new();
+    for name in ["Bartholomew Quigley", "Bartholomew Quill", "Rosalind Featherstone"] {
+        let user = user_repo.create(CreateUser {
+            name: name.to_string(),
+            email: format!("{}{}@example.com", name.to_lowercase().replace(' ', "."), suffix),
+            status: "active".to_string(),
+            department: department.clone(),
+        }).await.expect("Failed to create user");
+        users.push(user);
+    }
+    
+    let mut filters = std::collections::HashMap::new();
+    filters.insert("department".to_string(), department.clone());
+    let params = |query: &str| SearchParams {
+        query: Some(query.to_string()),
+        match_mode: MatchMode::Fuzzy,
+        filters: filters.clone(),
+        ..Default::default()
+    };
+    
+    // Typos still match; the default threshold leaves unrelated names out
+    let results = user_repo.search(params("Bartolomew Quigly")).await.expect("Failed fuzzy search");
+    assert_eq!(results.total_count, 2);
+    assert_eq!(user_repo.count(params("Rosalynd Fetherstone")).await.unwrap(), 1);
+    
+    // A stricter threshold keeps only the closest name
+    let results = user_repo.search(SearchParams {
+        similarity_threshold: Some(0.55),
+        ..params("Bartolomew Quigly")
+    }).await.expect("Failed fuzzy search with threshold");
+    assert_eq!(results.total_count, 1);
+    assert_eq!(results.items[0].name, "Bartholomew Quigley");
+    
+    // Ranked by similarity, best match first
+    let results = user_repo.search(SearchParams {
+        order_by_rank: true,
+        sort_by: Some("name".to_string()),
+        sort_order: SortOrder::Desc,
+        ..params("Bartholomew Quil")
+    }).await.expect("Failed ranked fuzzy search");
+    let names: Vec<_> = results.items.iter().map(|u| u.name.as_str()).collect();
+    assert_eq!(names, ["Bartholomew Quill", "Bartholomew Quigley"]);
+    assert!(results.next_cursor.is_none());
+    
+    let err = user_repo.search(SearchParams {
+        similarity_threshold: Some(1.5),
+        ..params("Bartholomew")
+    }).await.expect_err("Out-of-range threshold should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    
+    for user in users {
+        user_repo.hard_delete(user.id).await.expect("Failed to delete user");
+    }
+}
+
 #[tokio::test]
 async fn test_post_crud_without_soft_delete() {
     let pool = setup_test_db().await;
@@ -312,4 +1179,231 @@ async fn test_count_operations() {
         repo.hard_delete(user.id).await.expect("Failed to delete user");
     }
     cleanup_test_data(repo.pool()).await;
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_transaction_commit_and_rollback() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool.clone());
+    let suffix = get_unique_suffix();
+
+    // Rolled back: neither the user nor the post is persisted
+    let mut tx = pool.begin().await.expect("Failed to begin transaction");
+    let user = user_repo
+        .create_in(&mut tx, CreateUser {
+            name: format!("Tx Rollback User {}", suffix),
+            email: format!("tx_rollback_test{}@example.com", suffix),
+            status: "active".to_string(),
+            department: "test".to_string(),
+        })
+        .await
+        .expect("Failed to create user in transaction");
+    let post = post_repo
+        .create_in(&mut tx, CreatePost {
+            title: "Tx Test Post".to_string(),
+            content: "Rolled back".to_string(),
+            user_id: user.id,
+            published: false,
+        })
+        .await
+        .expect("Failed to create post in transaction");
+    assert!(user_repo.find_by_id_in(&mut tx, user.id).await.expect("Failed to query user").is_some());
+    tx.rollback().await.expect("Failed to roll back");
+
+    assert!(user_repo.find_by_id(user.id).await.expect("Failed to query user").is_none());
+    assert!(post_repo.find_by_id(post.id).await.expect("Failed to query post").is_none());
+
+    // Committed: updates and deletes made inside the transaction are visible afterwards
+    let mut tx = pool.begin().await.expect("Failed to begin transaction");
+    let user = user_repo
+        .create_in(&mut tx, CreateUser {
+            name: format!("Tx Commit User {}", suffix),
+            email: format!("tx_commit_test{}@example.com", suffix),
+            status: "active".to_string(),
+            department: "test".to_string(),
+        })
+        .await
+        .expect("Failed to create user in transaction");
+    user_repo
+        .update_in(&mut tx, user.id, UpdateUser { status: Some("inactive".to_string()), ..Default::default() })
+        .await
+        .expect("Failed to update user in transaction");
+    assert!(user_repo.delete_in(&mut tx, user.id).await.expect("Failed to delete user in transaction"));
+    let restored = user_repo
+        .restore_in(&mut tx, user.id)
+        .await
+        .expect("Failed to restore user in transaction");
+    assert!(restored.is_some());
+
+    let mut filters = std::collections::HashMap::new();
+    filters.insert("status".to_string(), "inactive".to_string());
+    let results = user_repo
+        .search_in(&mut tx, SearchParams {
+            query: Some(format!("tx_commit_test{}", suffix)),
+            filters,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to search in transaction");
+    assert_eq!(results.total_count, 1);
+    tx.commit().await.expect("Failed to commit");
+
+    let committed = user_repo.find_by_id(user.id).await.expect("Failed to query user");
+    assert_eq!(committed.expect("Committed user should exist").status, "inactive");
+
+    user_repo.hard_delete(user.id).await.expect("Failed to delete user");
+    cleanup_test_data(user_repo.pool()).await;
+}
+
+#[tokio::test]
+async fn test_unit_of_work_across_repositories() {
+    let pool = setup_test_db().await;
+    let suffix = get_unique_suffix();
+
+    let email = format!("uow_test{}@example.com", suffix);
+    let user = UnitOfWork::run(&pool, |uow| {
+        let email = email.clone();
+        Box::pin(async move {
+            let user = uow
+                .repo::<UserRepository>()
+                .create(CreateUser {
+                    name: format!("Uow User {}", suffix),
+                    email,
+                    status: "active".to_string(),
+                    department: "test".to_string(),
+                })
+                .await?;
+
+            // Work inside a rolled back savepoint is discarded, the rest is kept
+            let mut savepoint = uow.savepoint().await?;
+            savepoint
+                .repo::<UserRepository>()
+                .update(user.id, UpdateUser { status: Some("inactive".to_string()), ..Default::default() })
+                .await?;
+            savepoint.rollback().await?;
+
+            uow.repo::<PostRepository>()
+                .create(CreatePost {
+                    title: "Uow Test Post".to_string(),
+                    content: "Created in a unit of work".to_string(),
+                    user_id: user.id,
+                    published: true,
+                })
+                .await?;
+            Ok(user)
+        })
+    })
+    .await
+    .expect("Unit of work should commit");
+
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool.clone());
+    let stored = user_repo.find_by_id(user.id).await.expect("Failed to query user");
+    assert_eq!(stored.expect("Committed user should exist").status, "active");
+
+    let posts = post_repo.find_all().await.expect("Failed to list posts");
+    assert!(posts.iter().any(|p| p.user_id == user.id));
+
+    // An error rolls everything back
+    let result: RepositoryResult<i32> = UnitOfWork::run(&pool, |uow| {
+        Box::pin(async move {
+            uow.repo::<UserRepository>().hard_delete(user.id).await?;
+            Err(RepositoryError::conflict("abort"))
+        })
+    })
+    .await;
+    assert!(result.is_err());
+    assert!(user_repo.find_by_id(user.id).await.expect("Failed to query user").is_some());
+
+    for post in posts.into_iter().filter(|p| p.user_id == user.id) {
+        post_repo.delete(post.id).await.expect("Failed to delete post");
+    }
+    user_repo.hard_delete(user.id).await.expect("Failed to delete user");
+    cleanup_test_data(user_repo.pool()).await;
+}
+
+#[tokio::test]
+async fn test_unit_of_work_retries_serialization_failures() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    let pool = setup_test_db().await;
+    let suffix = get_unique_suffix();
+    let policy = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::from_millis(1),
+        ..Default::default()
+    };
+
+    // Fails with 40001 then 40P01 before succeeding on the third attempt
+    let attempts = AtomicU32::new(0);
+    let user = UnitOfWork::run_with_retry(&pool, &policy, |uow| {
+        let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        Box::pin(async move {
+            let user = uow
+                .repo::<UserRepository>()
+                .create(CreateUser {
+                    name: format!("Retry User {}", suffix),
+                    email: format!("retry_test{}@example.com", suffix),
+                    status: "active".to_string(),
+                    department: "test".to_string(),
+                })
+                .await?;
+
+            let errcode = match attempt {
+                1 => "serialization_failure",
+                2 => "deadlock_detected",
+                _ => return Ok(user),
+            };
+            sqlx::query(&format!("DO $$ BEGIN RAISE EXCEPTION 'conflict' USING ERRCODE = '{}'; END $$", errcode))
+                .execute(uow.connection())
+                .await?;
+            Ok(user)
+        })
+    })
+    .await
+    .expect("Transaction should succeed after retries");
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+    // Earlier attempts were rolled back, so the unique email was inserted only once
+    let user_repo = UserRepository::new(pool.clone());
+    let count = user_repo
+        .count(SearchParams {
+            query: Some(format!("retry_test{}@", suffix)),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to count users");
+    assert_eq!(count, 1);
+
+    // Running out of attempts reports the last cause
+    let result: RepositoryResult<()> = UnitOfWork::run_with_retry(&pool, &policy, |uow| {
+        Box::pin(async move {
+            sqlx::query("DO $$ BEGIN RAISE EXCEPTION 'conflict' USING ERRCODE = 'serialization_failure'; END $$")
+                .execute(uow.connection())
+                .await?;
+            Ok(())
+        })
+    })
+    .await;
+    match result {
+        Err(RepositoryError::RetriesExhausted { attempts, source }) => {
+            assert_eq!(attempts, 3);
+            assert_eq!(source.sqlstate().as_deref(), Some("40001"));
+        }
+        other => panic!("Expected RetriesExhausted, got {:?}", other.err()),
+    }
+
+    // Non-retryable errors are returned after a single attempt
+    let attempts = AtomicU32::new(0);
+    let result: RepositoryResult<()> = UnitOfWork::run_with_retry(&pool, &policy, |_uow| {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async move { Err(RepositoryError::validation("not transient")) })
+    })
+    .await;
+    assert!(matches!(result, Err(RepositoryError::Validation(_))));
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+    user_repo.hard_delete(user.id).await.expect("Failed to delete user");
+    cleanup_test_data(user_repo.pool()).await;
+}
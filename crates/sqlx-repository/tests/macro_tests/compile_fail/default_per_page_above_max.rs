@@ -0,0 +1,17 @@
+//! Test that the default page size cannot exceed the maximum
+
+use sqlx_repository::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "users")]
+#[repository(max_per_page = 50, default_per_page = 100)]  // This should fail: the default is above the maximum
+pub struct User {
+    pub id: i32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn main() {}
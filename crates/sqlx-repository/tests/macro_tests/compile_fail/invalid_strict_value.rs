@@ -0,0 +1,16 @@
+//! Test that strict only accepts a boolean
+
+use sqlx_repository::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "users", strict = "no")]  // This should fail: strict takes true or false
+pub struct User {
+    pub id: i32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+//! Test that the maximum page size is capped
+
+use sqlx_repository::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "users")]
+#[repository(max_per_page = 4294967295)]  // This should fail: every page could be billions of rows
+pub struct User {
+    pub id: i32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+//! Test that aggregatable fields must be fields of the struct
+
+use sqlx_repository::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "orders")]
+#[repository(aggregatable_fields(amount, tax))]  // This should fail: there is no tax field
+pub struct Order {
+    pub id: i32,
+    pub amount: f64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn main() {}
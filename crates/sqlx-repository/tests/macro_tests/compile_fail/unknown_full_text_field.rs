@@ -0,0 +1,18 @@
+//! Test that full-text fields must be fields of the struct
+
+use sqlx_repository::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "posts")]
+#[repository(full_text(config = "english", fields(title, body)))]  // This should fail: there is no body field
+pub struct Post {
+    pub id: i32,
+    pub title: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+//! Test that several repository settings can share one attribute
+
+use sqlx_repository::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "audit_log", strict = false, max_per_page = 50)]
+#[repository(soft_delete, searchable_fields(name), default_per_page = 5, filterable_fields(name, kind))]
+pub struct Event {
+    pub id: i32,
+    pub name: String,
+    pub kind: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+fn main() {
+    assert_eq!(EventRepository::table_name(), "audit_log");
+    assert!(!EventRepository::strict_params());
+    assert_eq!(EventRepository::max_per_page(), 50);
+    assert_eq!(EventRepository::default_per_page(), 5);
+    assert!(EventRepository::soft_delete_enabled());
+    assert_eq!(EventRepository::searchable_fields(), ["name"]);
+    assert_eq!(EventRepository::filterable_fields(), ["name", "kind"]);
+}
@@ -0,0 +1,1228 @@
+//! SQLite backend tests
+//!
+//! These tests run the derived repositories against an in-memory SQLite
+//! database, so they need no external server. Run them with:
+//!
+//! ```text
+//! cargo test --test sqlite_tests --features sqlite
+//! ```
+
+use sqlx_repository::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+use sqlx_repository::backends::sqlite::SqliteBackend;
+use sqlx_repository::backends::DatabaseBackend;
+use sqlx_repository::{FieldType, SqlValue};
+use std::collections::HashMap;
+
+/// Test User entity with soft delete on SQLite
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "users")]
+#[repository(backend = "sqlite")]
+#[repository(soft_delete)]
+#[repository(searchable_fields(name, email))]
+#[repository(filterable_fields(status, department))]
+pub struct User {
+    pub id: i32,
+    pub name: String,
+    pub email: String,
+    pub status: String,
+    pub department: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Test Post entity without soft delete on SQLite
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "posts")]
+#[repository(backend = "sqlite")]
+#[repository(searchable_fields(title, content))]
+#[repository(filterable_fields(published, user_id, created_at))]
+#[repository(sortable_fields(title, created_at))]
+pub struct Post {
+    pub id: i32,
+    pub title: String,
+    pub content: String,
+    pub user_id: i32,
+    pub published: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Test Event entity with a caller-supplied timestamp on SQLite
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "events")]
+#[repository(backend = "sqlite")]
+#[repository(filterable_fields(happened_at))]
+#[repository(sortable_fields(happened_at))]
+pub struct Event {
+    pub id: i32,
+    pub name: String,
+    pub happened_at: DateTime<Utc>,
+}
+
+/// Custom backend delegating to SQLite, to check the derive accepts backend paths
+pub struct CustomSqliteBackend;
+
+impl DatabaseBackend for CustomSqliteBackend {
+    type Database = sqlx::Sqlite;
+
+    const NAME: &'static str = "custom-sqlite";
+
+    const NULLS_SORT_LAST: bool = SqliteBackend::NULLS_SORT_LAST;
+
+    fn placeholder(index: usize) -> String {
+        <SqliteBackend as DatabaseBackend>::placeholder(index)
+    }
+
+    fn now() -> &'static str {
+        <SqliteBackend as DatabaseBackend>::now()
+    }
+
+    fn case_insensitive_like(column: &str, placeholder: &str) -> String {
+        SqliteBackend::case_insensitive_like(column, placeholder)
+    }
+
+    fn convert_type(rust_type: &str) -> &'static str {
+        SqliteBackend::convert_type(rust_type)
+    }
+
+    fn bind_value<'q>(args: &mut sqlx::sqlite::SqliteArguments<'q>, value: &SqlValue) -> RepositoryResult<()> {
+        SqliteBackend::bind_value(args, value)
+    }
+
+    fn decode_value(row: &sqlx::sqlite::SqliteRow, column: &str, field_type: FieldType) -> RepositoryResult<Option<SqlValue>> {
+        SqliteBackend::decode_value(row, column, field_type)
+    }
+
+    fn rows_affected(result: &sqlx::sqlite::SqliteQueryResult) -> u64 {
+        result.rows_affected()
+    }
+}
+
+/// Test Tag entity on a custom backend
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, Repository)]
+#[repository(table = "tags", strict = false)]
+#[repository(backend = "crate::CustomSqliteBackend")]
+#[repository(searchable_fields(label))]
+#[repository(max_per_page = 2, default_per_page = 1)]
+pub struct Tag {
+    pub id: i64,
+    pub label: String,
+}
+
+/// Create an in-memory database with the test schema
+///
+/// A single connection is used because every SQLite `:memory:` connection
+/// gets its own private database.
+async fn setup_sqlite_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to open in-memory SQLite database");
+
+    sqlx::query(
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            email TEXT UNIQUE NOT NULL,
+            status TEXT NOT NULL DEFAULT 'active',
+            department TEXT NOT NULL DEFAULT '',
+            created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+            updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+            deleted_at TEXT
+        )",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to create users table");
+
+    sqlx::query(
+        "CREATE TABLE posts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT NOT NULL,
+            content TEXT NOT NULL,
+            user_id INTEGER NOT NULL REFERENCES users(id),
+            published BOOLEAN NOT NULL DEFAULT FALSE,
+            created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+            updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+        )",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to create posts table");
+
+    sqlx::query("CREATE TABLE events (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, happened_at TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .expect("Failed to create events table");
+
+    sqlx::query("CREATE TABLE tags (id INTEGER PRIMARY KEY AUTOINCREMENT, label TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .expect("Failed to create tags table");
+
+    pool
+}
+
+fn new_user(name: &str, email: &str, status: &str, department: &str) -> CreateUser {
+    CreateUser {
+        name: name.to_string(),
+        email: email.to_string(),
+        status: status.to_string(),
+        department: department.to_string(),
+    }
+}
+
+/// Alice (active, engineering) and Bob (pending, marketing), each with one
+/// post that is published only for Alice
+async fn seed_posts(pool: &SqlitePool) -> (User, User) {
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool.clone());
+
+    let alice = user_repo
+        .create(new_user("Alice", "alice@example.com", "active", "engineering"))
+        .await
+        .expect("Failed to create user");
+    let bob = user_repo
+        .create(new_user("Bob", "bob@example.com", "pending", "marketing"))
+        .await
+        .expect("Failed to create user");
+    for (user, published) in [(&alice, true), (&bob, false)] {
+        post_repo
+            .create(CreatePost {
+                title: format!("{} post", user.name),
+                content: "content".to_string(),
+                user_id: user.id,
+                published,
+            })
+            .await
+            .expect("Failed to create post");
+    }
+    (alice, bob)
+}
+
+#[tokio::test]
+async fn test_sqlite_user_crud_operations() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    let user = repo
+        .create(new_user("Test User", "test@example.com", "active", "test"))
+        .await
+        .expect("Failed to create user");
+    assert_eq!(user.name, "Test User");
+    assert!(user.id > 0);
+
+    let found_user = repo.find_by_id(user.id).await.expect("Failed to find user");
+    assert_eq!(found_user.unwrap().email, "test@example.com");
+
+    let update_user = UpdateUser {
+        name: Some("Updated User".to_string()),
+        status: Some("inactive".to_string()),
+        ..Default::default()
+    };
+    let updated_user = repo
+        .update(user.id, update_user)
+        .await
+        .expect("Failed to update user")
+        .expect("Updated user should exist");
+    assert_eq!(updated_user.name, "Updated User");
+    assert_eq!(updated_user.status, "inactive");
+    assert_eq!(updated_user.email, "test@example.com");
+
+    assert!(repo.hard_delete(user.id).await.expect("Failed to delete user"));
+    assert!(repo.find_by_id(user.id).await.expect("Failed to query user").is_none());
+}
+
+#[tokio::test]
+async fn test_sqlite_soft_delete_operations() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    let user = repo
+        .create(new_user("Delete Test User", "delete_test@example.com", "active", "test"))
+        .await
+        .expect("Failed to create user");
+
+    assert!(repo.delete(user.id).await.expect("Failed to soft delete user"));
+    assert!(!repo.delete(user.id).await.expect("Failed to repeat soft delete"));
+    assert!(repo.find_by_id(user.id).await.expect("Failed to query user").is_none());
+
+    let deleted_results = repo
+        .search(SearchParams {
+            scope: RecordScope::Deleted,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to search deleted users");
+    assert_eq!(deleted_results.total_count, 1);
+    assert!(deleted_results.items[0].deleted_at.is_some());
+
+    let restored_user = repo.restore(user.id).await.expect("Failed to restore user");
+    assert!(restored_user.unwrap().deleted_at.is_none());
+    assert!(repo.find_by_id(user.id).await.expect("Failed to find restored user").is_some());
+}
+
+#[tokio::test]
+async fn test_sqlite_search_and_filtering() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    for user in [
+        new_user("Alice Engineering", "alice@example.com", "active", "engineering"),
+        new_user("Bob Marketing", "bob@example.com", "active", "marketing"),
+        new_user("Charlie Engineering", "charlie@example.com", "inactive", "engineering"),
+    ] {
+        repo.create(user).await.expect("Failed to create test user");
+    }
+
+    // Text search is case-insensitive across searchable fields
+    let search_results = repo
+        .search(SearchParams {
+            query: Some("alice".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to search users");
+    assert_eq!(search_results.total_count, 1);
+    assert_eq!(search_results.items[0].name, "Alice Engineering");
+
+    // Filters combine with text search
+    let mut filters = HashMap::new();
+    filters.insert("department".to_string(), "engineering".to_string());
+    let filter_results = repo
+        .search(SearchParams {
+            query: Some("ENGINEERING".to_string()),
+            filters: filters.clone(),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to filter users");
+    assert_eq!(filter_results.total_count, 2);
+    assert!(filter_results.items.iter().all(|u| u.department == "engineering"));
+
+    let count = repo
+        .count(SearchParams {
+            filters,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to count users");
+    assert_eq!(count, 2);
+
+    // Pagination and sorting
+    let page_results = repo
+        .search(SearchParams {
+            page: 1,
+            per_page: 1,
+            sort_by: Some("name".to_string()),
+            sort_order: SortOrder::Desc,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to paginate users");
+    assert_eq!(page_results.items.len(), 1);
+    assert_eq!(page_results.items[0].name, "Bob Marketing");
+    assert_eq!(page_results.total_pages, 3);
+}
+
+#[tokio::test]
+async fn test_sqlite_fuzzy_search_is_unsupported() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    // Trigram search is PostgreSQL-only
+    let err = repo
+        .search(SearchParams {
+            query: Some("Alise".to_string()),
+            match_mode: MatchMode::Fuzzy,
+            ..Default::default()
+        })
+        .await
+        .expect_err("Fuzzy search should be unsupported");
+    assert!(matches!(err, RepositoryError::UnsupportedFeature { .. }));
+    let err = repo.check_fuzzy_search().await.expect_err("Fuzzy search should be unsupported");
+    assert!(matches!(err, RepositoryError::UnsupportedFeature { .. }));
+}
+
+#[tokio::test]
+async fn test_sqlite_text_search_match_modes() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    for user in [
+        new_user("Alice Engineering", "alice@example.com", "active", "engineering"),
+        new_user("Bob Marketing", "bob@example.com", "active", "marketing"),
+        new_user("Charlie Engineering", "charlie@example.com", "inactive", "engineering"),
+        new_user("Dana 100%", "dana@example.com", "active", "sales_ops"),
+    ] {
+        repo.create(user).await.expect("Failed to create test user");
+    }
+
+    // Match modes, with wildcards in the query matched literally
+    let names = |match_mode: MatchMode, query: &str| {
+        let repo = &repo;
+        let query = query.to_string();
+        async move {
+            let results = repo
+                .search(SearchParams {
+                    query: Some(query),
+                    match_mode,
+                    sort_by: Some("name".to_string()),
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to search users");
+            results.items.into_iter().map(|u| u.name).collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(names(MatchMode::Contains, "0%").await, ["Dana 100%"]);
+    assert!(names(MatchMode::Contains, "a_i").await.is_empty());
+    assert_eq!(names(MatchMode::Prefix, "BOB").await, ["Bob Marketing"]);
+    assert_eq!(names(MatchMode::Suffix, "engineering").await, ["Alice Engineering", "Charlie Engineering"]);
+    assert_eq!(names(MatchMode::Exact, "bob marketing").await, ["Bob Marketing"]);
+    assert_eq!(names(MatchMode::CaseSensitive, "Engineering").await, ["Alice Engineering", "Charlie Engineering"]);
+    assert!(names(MatchMode::CaseSensitive, "ENGINEERING").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_sqlite_search_box_syntax() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    for user in [
+        new_user("Alice Engineering", "alice@example.com", "active", "engineering"),
+        new_user("Bob Marketing", "bob@example.com", "active", "marketing"),
+        new_user("Charlie Engineering", "charlie@example.com", "inactive", "engineering"),
+    ] {
+        repo.create(user).await.expect("Failed to create test user");
+    }
+
+    // Search-box syntax: free text plus field conditions
+    let params = SearchParams::from_search("engineering -status:inactive department:engineering,marketing")
+        .expect("Failed to parse search");
+    let results = repo.search(params).await.expect("Failed to search users");
+    assert_eq!(results.items.iter().map(|u| u.name.as_str()).collect::<Vec<_>>(), ["Alice Engineering"]);
+}
+
+#[tokio::test]
+async fn test_sqlite_distinct_values_and_suggest() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    for user in [
+        new_user("Alice Engineering", "alice@example.com", "active", "engineering"),
+        new_user("Bob Marketing", "bob@example.com", "active", "marketing"),
+        new_user("Charlie Engineering", "charlie@example.com", "inactive", "engineering"),
+    ] {
+        repo.create(user).await.expect("Failed to create test user");
+    }
+
+    // Typeahead over names, ignoring case, among the matches of the params
+    assert_eq!(repo.suggest("name", "alice", 5, SearchParams::default()).await.unwrap(), ["Alice Engineering"]);
+    let mut filters = HashMap::new();
+    filters.insert("status".to_string(), "inactive".to_string());
+    let suggestions = repo
+        .suggest(
+            "name",
+            "",
+            5,
+            SearchParams {
+                filters,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to suggest names");
+    assert_eq!(suggestions, ["Charlie Engineering"]);
+
+    let departments = repo
+        .distinct_values(
+            "department",
+            SearchParams {
+                value_order: ValueOrder::Alphabetical,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to list departments");
+    let departments: Vec<_> = departments.into_iter().map(|c| (c.value.unwrap().to_string(), c.count)).collect();
+    assert_eq!(
+        departments,
+        [("engineering".to_string(), 2), ("marketing".to_string(), 1)]
+    );
+}
+
+#[tokio::test]
+async fn test_sqlite_sort_by_is_validated() {
+    let pool = setup_sqlite_db().await;
+    let repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool);
+
+    // Only entity fields can be sorted by
+    let err = repo
+        .search(SearchParams {
+            sort_by: Some("name DESC, (SELECT 1)".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect_err("Unknown sort field should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+
+    // With sortable_fields, only those fields can be sorted by
+    let err = post_repo
+        .search(SearchParams {
+            sort_by: Some("content".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect_err("Non-sortable field should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    post_repo
+        .search(SearchParams {
+            sort_by: Some("created_at".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to sort by a sortable field");
+}
+
+#[tokio::test]
+async fn test_sqlite_filter_operators() {
+    let user_repo = UserRepository::new(setup_sqlite_db().await);
+
+    user_repo
+        .create(new_user("Alice", "alice@example.com", "active", "engineering"))
+        .await
+        .expect("Failed to create user");
+    user_repo
+        .create(new_user("Bob", "bob@example.com", "pending", "marketing"))
+        .await
+        .expect("Failed to create user");
+    user_repo
+        .create(new_user("Carol", "carol@example.com", "inactive", "engineering"))
+        .await
+        .expect("Failed to create user");
+
+    let count_where = |conditions: Vec<Filter>| {
+        let user_repo = &user_repo;
+        async move {
+            user_repo
+                .count(SearchParams {
+                    conditions,
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to count users")
+        }
+    };
+
+    assert_eq!(count_where(vec![Filter::ne("status", "active")]).await, 2);
+    assert_eq!(count_where(vec![Filter::is_in("status", ["active", "pending"])]).await, 2);
+    assert_eq!(count_where(vec![Filter::not_in("status", ["active", "pending"])]).await, 1);
+    assert_eq!(count_where(vec![Filter::is_in("status", Vec::<String>::new())]).await, 0);
+    assert_eq!(count_where(vec![Filter::not_in("status", Vec::<String>::new())]).await, 3);
+    assert_eq!(count_where(vec![Filter::starts_with("department", "eng")]).await, 2);
+    assert_eq!(count_where(vec![Filter::contains("department", "ket")]).await, 1);
+    assert_eq!(count_where(vec![Filter::contains("department", "_")]).await, 0);
+    assert_eq!(count_where(vec![Filter::gte("status", "inactive"), Filter::lte("status", "pending")]).await, 2);
+    assert_eq!(count_where(vec![Filter::between("status", "active", "inactive")]).await, 2);
+}
+
+#[tokio::test]
+async fn test_sqlite_page_size_limits() {
+    let pool = setup_sqlite_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let tag_repo = TagRepository::new(pool);
+
+    // Oversized pages and offsets past u32 are rejected
+    let err = user_repo
+        .search(SearchParams {
+            per_page: 5000,
+            ..Default::default()
+        })
+        .await
+        .expect_err("Oversized page should be rejected");
+    assert_eq!(err.to_string(), "Validation error: per_page must be between 1 and 1000, got 5000");
+    let err = user_repo
+        .suggest("name", "a", 5000, SearchParams::default())
+        .await
+        .expect_err("Oversized suggestion limit should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    let err = user_repo
+        .count(SearchParams {
+            page: u32::MAX,
+            ..Default::default()
+        })
+        .await
+        .expect_err("Out-of-range page should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    let err = user_repo
+        .distinct_values(
+            "status",
+            SearchParams {
+                page: u32::MAX,
+                cursor: Some("ignored".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect_err("Out-of-range value page should be rejected despite a cursor");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+
+    // Without strict params, page sizes default to default_per_page and are
+    // lowered to max_per_page, for suggestions too
+    for label in ["rust", "go", "zig"] {
+        tag_repo.create(CreateTag { label: label.to_string() }).await.expect("Failed to create tag");
+    }
+    let page_size = |per_page: u32| {
+        let tag_repo = &tag_repo;
+        async move {
+            let results = tag_repo
+                .search(SearchParams {
+                    per_page,
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to search tags");
+            (results.items.len(), results.per_page)
+        }
+    };
+    assert_eq!(page_size(0).await, (1, 1));
+    let results = tag_repo.search(SearchParams::default()).await.expect("Failed to search tags");
+    assert_eq!((results.items.len(), results.per_page), (1, 1));
+    assert_eq!(page_size(1000).await, (2, 2));
+    let suggestions = tag_repo
+        .suggest("label", "", 1000, SearchParams::default())
+        .await
+        .expect("Failed to suggest labels");
+    assert_eq!(suggestions.len(), 2);
+}
+
+#[tokio::test]
+async fn test_sqlite_unknown_filter_fields() {
+    let pool = setup_sqlite_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool.clone());
+    let tag_repo = TagRepository::new(pool);
+
+    // Filters on fields that are not filterable are rejected, naming the allowed ones
+    let mut filters = HashMap::new();
+    filters.insert("stauts".to_string(), "active".to_string());
+    let err = user_repo
+        .count(SearchParams {
+            filters,
+            conditions: vec![Filter::eq("name", "Alice")],
+            ..Default::default()
+        })
+        .await
+        .expect_err("Unknown filter fields should be rejected");
+    assert_eq!(
+        err.to_string(),
+        "Validation error: Unknown filter fields 'name', 'stauts' (allowed: 'status', 'department')"
+    );
+    let err = post_repo
+        .count(SearchParams {
+            scope: RecordScope::Deleted,
+            ..Default::default()
+        })
+        .await
+        .expect_err("Deleted scope without soft delete should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+
+    // Without strict params, unknown filters and the deleted scope are ignored
+    tag_repo
+        .create(CreateTag {
+            label: "rust".to_string(),
+        })
+        .await
+        .expect("Failed to create tag");
+    let mut filters = HashMap::new();
+    filters.insert("colour".to_string(), "red".to_string());
+    let count = tag_repo
+        .count(SearchParams {
+            filters,
+            conditions: vec![Filter::eq("label", "rust")],
+            scope: RecordScope::Deleted,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to count tags");
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn test_sqlite_filter_expression_trees() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    for user in [
+        new_user("Alice", "alice@example.com", "active", "engineering"),
+        new_user("Bob", "bob@example.com", "pending", "marketing"),
+        new_user("Carol", "carol@example.com", "inactive", "engineering"),
+    ] {
+        repo.create(user).await.expect("Failed to create user");
+    }
+
+    // Nested groups: (status = active OR status = pending) AND NOT department = marketing
+    let tree = repo
+        .search(SearchParams {
+            filter: Some(FilterExpr::and([
+                FilterExpr::or([Filter::eq("status", "active"), Filter::eq("status", "pending")]),
+                FilterExpr::not(Filter::eq("department", "marketing")),
+            ])),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to search with filter tree");
+    assert_eq!(tree.total_count, 1);
+    assert_eq!(tree.items[0].name, "Alice");
+
+    let err = repo
+        .count(SearchParams {
+            filter: Some(FilterExpr::not(Filter::eq("name", "Alice"))),
+            ..Default::default()
+        })
+        .await
+        .expect_err("Non-filterable field in a tree should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+}
+
+#[tokio::test]
+async fn test_sqlite_filters_bind_column_types() {
+    let pool = setup_sqlite_db().await;
+    let (alice, bob) = seed_posts(&pool).await;
+    let post_repo = PostRepository::new(pool);
+
+    // Typed values bind as their own SQL type
+    let published = post_repo
+        .search(SearchParams {
+            conditions: vec![Filter::eq("published", true), Filter::gt("user_id", 0)],
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to filter posts");
+    assert_eq!(published.total_count, 1);
+    assert_eq!(published.items[0].user_id, alice.id);
+
+    // String filters are parsed as the column's type before binding
+    let mut filters = HashMap::new();
+    filters.insert("published".to_string(), "false".to_string());
+    filters.insert("user_id".to_string(), bob.id.to_string());
+    let count = post_repo
+        .count(SearchParams {
+            filters: filters.clone(),
+            conditions: vec![Filter::lt("created_at", (Utc::now() + chrono::Duration::minutes(1)).to_rfc3339())],
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to filter posts");
+    assert_eq!(count, 1);
+    let count = post_repo
+        .count(SearchParams {
+            filters: filters.clone(),
+            conditions: vec![Filter::gt("created_at", (Utc::now() + chrono::Duration::minutes(1)).to_rfc3339())],
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to filter posts");
+    assert_eq!(count, 0);
+
+    filters.insert("published".to_string(), "maybe".to_string());
+    let err = post_repo
+        .count(SearchParams {
+            filters,
+            ..Default::default()
+        })
+        .await
+        .expect_err("Unparseable boolean should be rejected");
+    assert!(matches!(err, RepositoryError::Validation(_)));
+}
+
+#[tokio::test]
+async fn test_sqlite_range_filters() {
+    let pool = setup_sqlite_db().await;
+    let (alice, _) = seed_posts(&pool).await;
+    let post_repo = PostRepository::new(pool);
+    // Range bounds on timestamps, relative to now, and on integers
+    let count_range = |bounds: [(&str, &str); 2]| {
+        let post_repo = &post_repo;
+        let filters = bounds.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+        async move {
+            post_repo
+                .count(SearchParams {
+                    filters,
+                    ..Default::default()
+                })
+                .await
+                .expect("Failed to count posts in range")
+        }
+    };
+    assert_eq!(count_range([("created_at_from", "now-1h"), ("created_at_to", "now+1h")]).await, 2);
+    assert_eq!(count_range([("created_at_from", "now+1h"), ("user_id_gte", "0")]).await, 0);
+    let alice_id = alice.id.to_string();
+    assert_eq!(count_range([("user_id_from", &alice_id), ("user_id_to", &alice_id)]).await, 1);
+}
+
+#[tokio::test]
+async fn test_sqlite_facet_counts() {
+    let pool = setup_sqlite_db().await;
+    let (alice, bob) = seed_posts(&pool).await;
+    let post_repo = PostRepository::new(pool);
+    // Facets decode each value as its column's type
+    let facets = post_repo
+        .facets(SearchParams {
+            facets: vec!["published".to_string(), "user_id".to_string()],
+            conditions: vec![Filter::eq("published", true)],
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to count facets");
+    let published_counts: Vec<_> = facets["published"].iter().map(|f| (f.value.clone(), f.count)).collect();
+    assert_eq!(
+        published_counts,
+        [(Some(FilterValue::Bool(false)), 1), (Some(FilterValue::Bool(true)), 1)]
+    );
+    let user_counts: Vec<_> = facets["user_id"].iter().map(|f| (f.value.clone(), f.count)).collect();
+    assert_eq!(user_counts, [(Some(FilterValue::Int(alice.id.into())), 1)]);
+
+    // A facet's conditions in the filter tree are lifted too, with the `not` around them
+    let facets = post_repo
+        .facets(SearchParams {
+            facets: vec!["published".to_string(), "user_id".to_string()],
+            filter: Some(FilterExpr::and([
+                FilterExpr::from(Filter::eq("user_id", alice.id)),
+                FilterExpr::not(Filter::eq("published", true)),
+            ])),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to count facets");
+    let published_counts: Vec<_> = facets["published"].iter().map(|f| (f.value.clone(), f.count)).collect();
+    assert_eq!(published_counts, [(Some(FilterValue::Bool(true)), 1)]);
+    let user_counts: Vec<_> = facets["user_id"].iter().map(|f| (f.value.clone(), f.count)).collect();
+    assert_eq!(user_counts, [(Some(FilterValue::Int(bob.id.into())), 1)]);
+}
+
+#[tokio::test]
+async fn test_sqlite_aggregates() {
+    let pool = setup_sqlite_db().await;
+    let (alice, _) = seed_posts(&pool).await;
+    let post_repo = PostRepository::new(pool);
+    // Aggregates cast to SQLite's INTEGER and REAL and decode dates by field type
+    let rows = post_repo
+        .aggregate(
+            SearchParams::default(),
+            AggregateQuery::new([
+                Aggregate::count(),
+                Aggregate::sum("user_id"),
+                Aggregate::avg("user_id"),
+                Aggregate::max("created_at"),
+            ])
+            .group_by(["published"]),
+        )
+        .await
+        .expect("Failed to aggregate posts");
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1].group["published"], Some(FilterValue::Bool(true)));
+    assert_eq!(rows[1].get_i64("count"), Some(1));
+    assert_eq!(rows[1].get_i64("sum_user_id"), Some(alice.id.into()));
+    assert_eq!(rows[1].get_f64("avg_user_id"), Some(alice.id.into()));
+    assert!(matches!(rows[1].values["max_created_at"], Some(FilterValue::Text(_))));
+
+    // Only count may leave out the field, as in `{"func": "sum"}`
+    let sum_rows: Aggregate = serde_json::from_str(r#"{"func": "sum"}"#).unwrap();
+    for query in [
+        AggregateQuery::new([sum_rows]),
+        AggregateQuery::new([Aggregate::avg("title")]),
+        AggregateQuery::new([Aggregate::count()]).group_by(["title"]),
+        AggregateQuery::new([Aggregate::max("user_id"), Aggregate::min("user_id").alias("max_user_id")]),
+    ] {
+        let err = post_repo
+            .aggregate(SearchParams::default(), query)
+            .await
+            .expect_err("Invalid aggregate should be rejected");
+        assert!(matches!(err, RepositoryError::Validation(_)));
+    }
+}
+
+#[tokio::test]
+async fn test_sqlite_cursor_pagination() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    let mut users = Vec::new();
+    for (i, status) in ["active", "pending", "active", "inactive", "active", "pending", "inactive"].iter().enumerate() {
+        let user = repo
+            .create(new_user(&format!("User {}", i), &format!("user{}@example.com", i), status, "engineering"))
+            .await
+            .expect("Failed to create user");
+        users.push(user);
+    }
+    // Sorting on deleted_at mixes NULLs with timestamps
+    repo.delete(users[1].id).await.expect("Failed to delete user");
+    repo.delete(users[4].id).await.expect("Failed to delete user");
+
+    // Offset pages past the end still report the total
+    let past_end = repo
+        .search(SearchParams {
+            page: 4,
+            per_page: 2,
+            scope: RecordScope::All,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to fetch page past the end");
+    assert!(past_end.items.is_empty());
+    assert_eq!(past_end.total_count, 7);
+
+    for (sort_by, sort_order) in [("status", SortOrder::Desc), ("status", SortOrder::Asc), ("deleted_at", SortOrder::Asc), ("deleted_at", SortOrder::Desc)] {
+        let params = |cursor: Option<String>| SearchParams {
+            cursor,
+            per_page: 2,
+            sort_by: Some(sort_by.to_string()),
+            sort_order,
+            scope: RecordScope::All,
+            ..Default::default()
+        };
+
+        // Forwards through every page
+        let mut pages = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = repo.search(params(cursor)).await.expect("Failed to fetch page");
+            assert_eq!(page.total_count, 7);
+            pages.push(page.items.iter().map(|u| u.id).collect::<Vec<_>>());
+            assert_eq!(page.prev_cursor.is_some(), pages.len() > 1);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(pages.len(), 4);
+
+        // Same order as a plain sort with id as tie-breaker
+        let ids: Vec<i32> = pages.concat();
+        let all = repo
+            .search(SearchParams {
+                per_page: 10,
+                ..params(None)
+            })
+            .await
+            .expect("Failed to fetch all users");
+        assert_eq!(ids, all.items.iter().map(|u| u.id).collect::<Vec<_>>());
+        let mut sorted = ids.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 7);
+
+        // Backwards from the last page returns the same pages in reverse
+        let mut page = repo.search(params(None)).await.expect("Failed to fetch page");
+        for _ in 1..pages.len() {
+            page = repo.search(params(page.next_cursor.clone())).await.expect("Failed to fetch page");
+        }
+        for expected in pages.iter().rev().skip(1) {
+            page = repo.search(params(page.prev_cursor.clone())).await.expect("Failed to fetch page");
+            assert_eq!(&page.items.iter().map(|u| u.id).collect::<Vec<_>>(), expected);
+        }
+        assert!(page.prev_cursor.is_none());
+        assert!(page.next_cursor.is_some());
+    }
+}
+
+#[tokio::test]
+async fn test_sqlite_count_modes() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    for i in 0..5 {
+        repo.create(new_user(&format!("User {}", i), &format!("user{}@example.com", i), "active", "engineering"))
+            .await
+            .expect("Failed to create user");
+    }
+
+    // Skipping the count reads one row ahead instead
+    let uncounted = repo
+        .search(SearchParams {
+            per_page: 2,
+            count_mode: CountMode::None,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to skip count");
+    assert_eq!((uncounted.total_count, uncounted.count_mode), (2, CountMode::None));
+    assert!(uncounted.has_next_page());
+
+    // A short last page still knows the exact total
+    let last = repo
+        .search(SearchParams {
+            page: 2,
+            per_page: 2,
+            count_mode: CountMode::None,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to fetch last page");
+    assert_eq!((last.total_count, last.count_mode), (5, CountMode::Exact));
+    assert!(!last.has_next_page());
+
+    // SQLite has no planner estimate to report
+    let err = repo
+        .search(SearchParams {
+            per_page: 2,
+            count_mode: CountMode::Estimated,
+            ..Default::default()
+        })
+        .await
+        .expect_err("Estimated count should be unsupported");
+    assert!(matches!(err, RepositoryError::UnsupportedFeature { .. }));
+}
+
+#[tokio::test]
+async fn test_sqlite_multi_column_sort() {
+    let repo = UserRepository::new(setup_sqlite_db().await);
+
+    for (name, department, deleted) in [
+        ("Ann", "sales", false),
+        ("Ben", "eng", true),
+        ("Cat", "eng", false),
+        ("Dan", "sales", true),
+        ("Eve", "eng", false),
+        ("Fay", "eng", true),
+    ] {
+        let user = repo
+            .create(new_user(name, &format!("{}@example.com", name.to_lowercase()), "active", department))
+            .await
+            .expect("Failed to create user");
+        if deleted {
+            repo.delete(user.id).await.expect("Failed to delete user");
+        }
+    }
+
+    let params = |sort: &str, cursor: Option<String>, per_page: u32| SearchParams {
+        sort: SortKey::parse_list(sort).unwrap(),
+        scope: RecordScope::All,
+        cursor,
+        per_page,
+        ..Default::default()
+    };
+
+    // SQLite puts NULLs first in ascending order unless told otherwise
+    for (sort, expected) in [
+        ("department,deleted_at,name", ["Cat", "Eve", "Ben", "Fay", "Ann", "Dan"]),
+        ("department,deleted_at:nulls_last,name", ["Ben", "Fay", "Cat", "Eve", "Dan", "Ann"]),
+        ("department,-deleted_at:nulls_first,-name", ["Eve", "Cat", "Fay", "Ben", "Ann", "Dan"]),
+    ] {
+        let results = repo.search(params(sort, None, 10)).await.expect("Failed to sort by several columns");
+        let names: Vec<&str> = results.items.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, expected, "sort {}", sort);
+
+        // Cursors follow the same order
+        let mut ids = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = repo.search(params(sort, cursor, 2)).await.expect("Failed to fetch page");
+            ids.extend(page.items.iter().map(|u| u.id));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(ids, results.items.iter().map(|u| u.id).collect::<Vec<_>>(), "sort {}", sort);
+    }
+}
+
+#[tokio::test]
+async fn test_sqlite_timestamp_filters_and_cursors() {
+    let repo = EventRepository::new(setup_sqlite_db().await);
+
+    let noon: DateTime<Utc> = "2026-03-01T12:00:00Z".parse().unwrap();
+    for (name, happened_at) in [("launch", noon), ("demo", noon), ("kickoff", noon - chrono::Duration::days(30))] {
+        repo.create(CreateEvent {
+            name: name.to_string(),
+            happened_at,
+        })
+        .await
+        .expect("Failed to create event");
+    }
+
+    // Filter values are bound the way sqlx stores the column
+    for filter in [Filter::eq("happened_at", "2026-03-01T12:00:00Z"), Filter::gte("happened_at", "2026-03-01T12:00:00Z")] {
+        let count = repo
+            .count(SearchParams {
+                conditions: vec![filter],
+                ..Default::default()
+            })
+            .await
+            .expect("Failed to count events");
+        assert_eq!(count, 2);
+    }
+
+    // Cursors on the timestamp round-trip through the same binding
+    let params = |cursor: Option<String>| SearchParams {
+        cursor,
+        per_page: 1,
+        sort: SortKey::parse_list("-happened_at").unwrap(),
+        ..Default::default()
+    };
+    let mut names = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = repo.search(params(cursor)).await.expect("Failed to fetch page");
+        names.extend(page.items.iter().map(|e| e.name.clone()));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    // Equal timestamps fall back to id in the same direction
+    assert_eq!(names, ["demo", "launch", "kickoff"]);
+}
+
+#[tokio::test]
+async fn test_sqlite_post_crud_without_soft_delete() {
+    let pool = setup_sqlite_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool);
+
+    let user = user_repo
+        .create(new_user("Post Test User", "post_test@example.com", "active", "test"))
+        .await
+        .expect("Failed to create user");
+
+    let post = post_repo
+        .create(CreatePost {
+            title: "Test Post".to_string(),
+            content: "This is a test post content".to_string(),
+            user_id: user.id,
+            published: false,
+        })
+        .await
+        .expect("Failed to create post");
+    assert_eq!(post.user_id, user.id);
+    assert!(!post.published);
+
+    let all_posts = post_repo.find_all().await.expect("Failed to list posts");
+    assert_eq!(all_posts.len(), 1);
+
+    assert!(post_repo.delete(post.id).await.expect("Failed to delete post"));
+    assert!(post_repo.find_by_id(post.id).await.expect("Failed to query post").is_none());
+    assert!(post_repo.restore(post.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_sqlite_custom_backend_path() {
+    let repo = TagRepository::new(setup_sqlite_db().await);
+
+    let tag = repo
+        .create(CreateTag { label: "rust".to_string() })
+        .await
+        .expect("Failed to create tag");
+    assert_eq!(tag.label, "rust");
+
+    let updated = repo
+        .update(tag.id, UpdateTag { label: Some("sql".to_string()) })
+        .await
+        .expect("Failed to update tag")
+        .expect("Updated tag should exist");
+    assert_eq!(updated.label, "sql");
+    assert!(repo.delete(tag.id).await.expect("Failed to delete tag"));
+}
+
+#[tokio::test]
+async fn test_sqlite_transaction_rollback() {
+    let pool = setup_sqlite_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let post_repo = PostRepository::new(pool.clone());
+
+    let mut tx = pool.begin().await.expect("Failed to begin transaction");
+    let user = user_repo
+        .create_in(&mut tx, new_user("Tx User", "tx@example.com", "active", "test"))
+        .await
+        .expect("Failed to create user in transaction");
+    post_repo
+        .create_in(&mut tx, CreatePost {
+            title: "Tx Post".to_string(),
+            content: "Rolled back".to_string(),
+            user_id: user.id,
+            published: false,
+        })
+        .await
+        .expect("Failed to create post in transaction");
+    assert_eq!(post_repo.find_all_in(&mut tx).await.expect("Failed to list posts").len(), 1);
+    tx.rollback().await.expect("Failed to roll back");
+
+    // The pool has a single connection, so it is only usable again once the transaction ends
+    assert_eq!(user_repo.count(SearchParams::default()).await.expect("Failed to count users"), 0);
+    assert!(post_repo.find_all().await.expect("Failed to list posts").is_empty());
+}
+
+#[tokio::test]
+async fn test_sqlite_unit_of_work() {
+    let pool = setup_sqlite_db().await;
+
+    // Committed on Ok, including work kept by a released savepoint
+    let user = UnitOfWork::run(&pool, |uow| {
+        Box::pin(async move {
+            let user = uow
+                .repo::<UserRepository>()
+                .create(new_user("Uow User", "uow@example.com", "active", "test"))
+                .await?;
+
+            let mut savepoint = uow.savepoint().await?;
+            savepoint
+                .repo::<PostRepository>()
+                .create(CreatePost {
+                    title: "Kept".to_string(),
+                    content: "Released savepoint".to_string(),
+                    user_id: user.id,
+                    published: true,
+                })
+                .await?;
+            savepoint.commit().await?;
+
+            // A savepoint dropped without commit is rolled back on its own
+            let mut savepoint = uow.savepoint().await?;
+            savepoint
+                .repo::<PostRepository>()
+                .create(CreatePost {
+                    title: "Discarded".to_string(),
+                    content: "Dropped savepoint".to_string(),
+                    user_id: user.id,
+                    published: false,
+                })
+                .await?;
+            drop(savepoint);
+
+            assert_eq!(uow.repo::<PostRepository>().find_all().await?.len(), 1);
+            Ok(user)
+        })
+    })
+    .await
+    .expect("Unit of work should commit");
+
+    let post_repo = PostRepository::new(pool.clone());
+    let posts = post_repo.find_all().await.expect("Failed to list posts");
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].title, "Kept");
+    assert_eq!(posts[0].user_id, user.id);
+
+    // Rolled back on Err
+    let result: RepositoryResult<()> = UnitOfWork::run(&pool, |uow| {
+        Box::pin(async move {
+            uow.repo::<UserRepository>()
+                .create(new_user("Failed User", "failed@example.com", "active", "test"))
+                .await?;
+            Err(RepositoryError::validation("abort"))
+        })
+    })
+    .await;
+    assert!(matches!(result, Err(RepositoryError::Validation(_))));
+
+    let user_repo = UserRepository::new(pool.clone());
+    assert_eq!(user_repo.count(SearchParams::default()).await.expect("Failed to count users"), 1);
+
+    // Rolled back on drop
+    let mut uow = UnitOfWork::begin(&pool).await.expect("Failed to begin unit of work");
+    uow.repo::<UserRepository>()
+        .delete(user.id)
+        .await
+        .expect("Failed to delete user in unit of work");
+    drop(uow);
+    assert!(user_repo.find_by_id(user.id).await.expect("Failed to query user").is_some());
+}
@@ -3,6 +3,9 @@
 //! This module provides utilities for setting up test databases with proper migrations
 //! and cleanup functionality.
 
+// Each test binary includes this module but only uses part of it
+#![allow(dead_code)]
+
 use sqlx::{PgPool, migrate::MigrateDatabase, Postgres};
 use std::env;
 use std::sync::atomic::{AtomicU32, Ordering};
@@ -0,0 +1,32 @@
+//! Unit tests for aggregate queries
+
+use sqlx_repository::{Aggregate, AggregateFunc, AggregateQuery, FieldType};
+
+#[test]
+fn test_aggregate_names() {
+    assert_eq!(Aggregate::count().name(), "count");
+    assert_eq!(Aggregate::sum("amount").name(), "sum_amount");
+    assert_eq!(Aggregate::count_field("email").name(), "count_email");
+    assert_eq!(Aggregate::avg("amount").alias("mean").name(), "mean");
+}
+
+#[test]
+fn test_aggregate_result_types() {
+    assert_eq!(AggregateFunc::Count.result_type(FieldType::Text), FieldType::I64);
+    assert_eq!(AggregateFunc::Sum.result_type(FieldType::I32), FieldType::I64);
+    assert_eq!(AggregateFunc::Sum.result_type(FieldType::F32), FieldType::F64);
+    assert_eq!(AggregateFunc::Avg.result_type(FieldType::I16), FieldType::F64);
+    assert_eq!(AggregateFunc::Max.result_type(FieldType::DateTime), FieldType::DateTime);
+}
+
+#[test]
+fn test_aggregate_query_deserialization() {
+    let query: AggregateQuery = serde_json::from_str(r#"{
+        "aggregates": [{"func": "count"}, {"func": "sum", "field": "amount", "alias": "total"}],
+        "group_by": ["status"]
+    }"#).unwrap();
+    assert_eq!(
+        query,
+        AggregateQuery::new([Aggregate::count(), Aggregate::sum("amount").alias("total")]).group_by(["status"])
+    );
+}
@@ -128,4 +128,451 @@ fn test_postgres_query_building_consistency() {
     assert!(update_query.contains("$1"));
     assert!(update_query.contains("$2"));
     assert!(update_query.contains("$3")); // WHERE id = $3
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_postgres_dialect() {
+    use sqlx_repository::backends::DatabaseBackend;
+
+    assert_eq!(<PostgresBackend as DatabaseBackend>::placeholder(2), "$2");
+    assert_eq!(<PostgresBackend as DatabaseBackend>::now(), "NOW()");
+    assert_eq!(PostgresBackend::case_insensitive_like("name", "$1"), "name ILIKE $1");
+}
+
+#[test]
+fn test_postgres_quote_identifier() {
+    use sqlx_repository::backends::DatabaseBackend;
+
+    assert_eq!(PostgresBackend::quote_identifier("users"), "\"users\"");
+    assert_eq!(PostgresBackend::quote_identifier("app.users"), "\"app\".\"users\"");
+    // Embedded quotes are doubled so the name cannot break out of the identifier
+    assert_eq!(PostgresBackend::quote_identifier("na\"me"), "\"na\"\"me\"");
+}
+
+#[test]
+fn test_postgres_crud_sql() {
+    use sqlx_repository::backends::DatabaseBackend;
+
+    assert_eq!(
+        PostgresBackend::select_by_id_sql("users", true),
+        "SELECT * FROM \"users\" WHERE \"id\" = $1 AND \"deleted_at\" IS NULL"
+    );
+    assert_eq!(
+        PostgresBackend::select_all_sql("posts", false),
+        "SELECT * FROM \"posts\" ORDER BY \"id\""
+    );
+    assert_eq!(
+        PostgresBackend::insert_sql("users", &["name", "email"], true),
+        "INSERT INTO \"users\" (\"name\", \"email\", \"created_at\", \"updated_at\") VALUES ($1, $2, NOW(), NOW()) RETURNING *"
+    );
+    assert_eq!(
+        PostgresBackend::update_sql("users", &["name"], true),
+        "UPDATE \"users\" SET \"name\" = $1, \"updated_at\" = NOW() WHERE \"id\" = $2 AND \"deleted_at\" IS NULL RETURNING *"
+    );
+    assert_eq!(
+        PostgresBackend::update_sql("posts", &["title", "content"], false),
+        "UPDATE \"posts\" SET \"title\" = $1, \"content\" = $2 WHERE \"id\" = $3 RETURNING *"
+    );
+    assert_eq!(
+        PostgresBackend::delete_sql("users", true),
+        "UPDATE \"users\" SET \"deleted_at\" = NOW(), \"updated_at\" = NOW() WHERE \"id\" = $1 AND \"deleted_at\" IS NULL"
+    );
+    assert_eq!(PostgresBackend::delete_sql("users", false), "DELETE FROM \"users\" WHERE \"id\" = $1");
+    assert_eq!(
+        PostgresBackend::restore_sql("users"),
+        "UPDATE \"users\" SET \"deleted_at\" = NULL, \"updated_at\" = NOW() WHERE \"id\" = $1 RETURNING *"
+    );
+}
+
+#[test]
+fn test_postgres_search_sql() {
+    use sqlx_repository::backends::DatabaseBackend;
+    use sqlx_repository::SortKey;
+
+    let where_clause = " WHERE \"status\" = $1";
+    assert_eq!(
+        PostgresBackend::count_sql("users", where_clause),
+        "SELECT COUNT(*) FROM \"users\" WHERE \"status\" = $1"
+    );
+    let order_by = [PostgresBackend::order_by_sql(&SortKey::desc("name"))];
+    assert_eq!(
+        PostgresBackend::select_page_sql("users", &[], where_clause, &order_by, 20, Some(40)),
+        "SELECT * FROM \"users\" WHERE \"status\" = $1 ORDER BY \"name\" DESC LIMIT 20 OFFSET 40"
+    );
+
+    // Sort columns are quoted, so they cannot inject SQL
+    let order_by = [PostgresBackend::order_by_sql(&SortKey::asc("id; DROP TABLE users"))];
+    let query = PostgresBackend::select_page_sql("users", &[], "", &order_by, 10, Some(0));
+    assert_eq!(query, "SELECT * FROM \"users\" ORDER BY \"id; DROP TABLE users\" ASC LIMIT 10 OFFSET 0");
+}
+
+#[test]
+fn test_escape_like() {
+    use sqlx_repository::backends::escape_like;
+
+    assert_eq!(escape_like("plain"), "plain");
+    assert_eq!(escape_like("50%"), "50!%");
+    assert_eq!(escape_like("a_b!"), "a!_b!!");
+}
+
+#[test]
+fn test_postgres_match_modes() {
+    use sqlx_repository::backends::DatabaseBackend;
+    use sqlx_repository::MatchMode;
+
+    assert_eq!(PostgresBackend::match_pattern("50%", MatchMode::Contains), "%50!%%");
+    assert_eq!(PostgresBackend::match_pattern("a_b", MatchMode::Prefix), "a!_b%");
+    assert_eq!(PostgresBackend::match_pattern("a_b", MatchMode::Suffix), "%a!_b");
+    assert_eq!(PostgresBackend::match_pattern("a_b", MatchMode::Exact), "a!_b");
+    assert_eq!(
+        PostgresBackend::match_sql("\"name\"", "$1", MatchMode::Exact),
+        "\"name\" ILIKE $1 ESCAPE '!'"
+    );
+    assert_eq!(
+        PostgresBackend::match_sql("\"name\"", "$1", MatchMode::CaseSensitive),
+        "\"name\" LIKE $1 ESCAPE '!'"
+    );
+}
+
+#[test]
+fn test_postgres_full_text_sql() {
+    use sqlx_repository::backends::DatabaseBackend;
+
+    let document = "to_tsvector('english', coalesce(\"title\", '') || ' ' || coalesce(\"content\", ''))";
+    assert_eq!(
+        PostgresBackend::full_text_match_sql("english", &["title", "content"], "$1").unwrap(),
+        format!("{} @@ websearch_to_tsquery('english', $1)", document)
+    );
+    assert_eq!(
+        PostgresBackend::full_text_rank_sql("english", &["title", "content"], "$2").unwrap(),
+        format!("ts_rank({}, websearch_to_tsquery('english', $2))", document)
+    );
+    assert_eq!(
+        PostgresBackend::full_text_headline_sql("simple", "title", "$3").unwrap(),
+        "ts_headline('simple', coalesce(\"title\", ''), websearch_to_tsquery('simple', $3))"
+    );
+
+    // Extra columns follow the table's own
+    assert_eq!(
+        PostgresBackend::select_page_sql("posts", &["1 AS \"one\"".to_string()], "", &["\"id\" ASC".to_string()], 5, Some(10)),
+        "SELECT *, 1 AS \"one\" FROM \"posts\" ORDER BY \"id\" ASC LIMIT 5 OFFSET 10"
+    );
+}
+
+#[test]
+fn test_postgres_similarity_sql() {
+    use sqlx_repository::backends::DatabaseBackend;
+
+    assert_eq!(
+        PostgresBackend::similarity_match_sql(&["name", "email"], "$1", None).unwrap(),
+        "(\"name\" % $1 OR \"email\" % $1)"
+    );
+    assert_eq!(
+        PostgresBackend::similarity_match_sql(&["name", "email"], "$1", Some("$2")).unwrap(),
+        "greatest(similarity(\"name\", $1), similarity(\"email\", $1)) >= $2"
+    );
+    assert_eq!(
+        PostgresBackend::similarity_score_sql(&["name"], "$3").unwrap(),
+        "greatest(similarity(\"name\", $3))"
+    );
+    assert!(PostgresBackend::fuzzy_search_check_sql().unwrap().contains("pg_trgm"));
+}
+
+#[test]
+fn test_postgres_estimated_count_sql() {
+    use sqlx_repository::backends::{plan_rows_estimate, DatabaseBackend};
+
+    assert_eq!(
+        PostgresBackend::estimated_count_sql("users", " WHERE \"status\" = $1").unwrap(),
+        "EXPLAIN SELECT * FROM \"users\" WHERE \"status\" = $1"
+    );
+    assert_eq!(plan_rows_estimate("Seq Scan on users  (cost=0.00..12.10 rows=210 width=164)"), Some(210));
+    assert_eq!(plan_rows_estimate("-> Table scan on users  (cost=0.75 rows=1.2e+6)"), Some(1_200_000));
+    assert_eq!(plan_rows_estimate("-> Filter: (users.id = 1)  (cost=0.35 rows=0.5)"), Some(1));
+    assert_eq!(plan_rows_estimate("Result"), None);
+}
+
+#[test]
+fn test_postgres_facet_sql() {
+    use sqlx_repository::backends::DatabaseBackend;
+
+    assert_eq!(
+        PostgresBackend::facet_sql("users", "status", " WHERE \"deleted_at\" IS NULL"),
+        "SELECT \"status\" AS \"value\", COUNT(*) AS \"count\" FROM \"users\" WHERE \"deleted_at\" IS NULL \
+         GROUP BY \"status\" ORDER BY COUNT(*) DESC, \"status\""
+    );
+}
+
+#[test]
+fn test_postgres_aggregate_sql() {
+    use sqlx_repository::backends::DatabaseBackend;
+    use sqlx_repository::{AggregateFunc, FieldType};
+
+    assert_eq!(PostgresBackend::aggregate_term_sql(AggregateFunc::Count, None, FieldType::I64), "COUNT(*)");
+    assert_eq!(
+        PostgresBackend::aggregate_term_sql(AggregateFunc::Sum, Some("amount"), FieldType::I32),
+        "CAST(SUM(\"amount\") AS BIGINT)"
+    );
+    assert_eq!(
+        PostgresBackend::aggregate_term_sql(AggregateFunc::Avg, Some("amount"), FieldType::I32),
+        "CAST(AVG(\"amount\") AS DOUBLE PRECISION)"
+    );
+    assert_eq!(
+        PostgresBackend::aggregate_term_sql(AggregateFunc::Max, Some("created_at"), FieldType::DateTime),
+        "MAX(\"created_at\")"
+    );
+    assert_eq!(
+        PostgresBackend::aggregate_sql("orders", &["\"status\"".to_string(), "COUNT(*) AS \"n\"".to_string()], "", &["status".to_string()]),
+        "SELECT \"status\", COUNT(*) AS \"n\" FROM \"orders\" GROUP BY \"status\" ORDER BY \"status\""
+    );
+}
+
+#[test]
+fn test_postgres_distinct_values_sql() {
+    use sqlx_repository::backends::DatabaseBackend;
+    use sqlx_repository::ValueOrder;
+
+    assert_eq!(
+        PostgresBackend::distinct_values_sql("users", "department", "", ValueOrder::Alphabetical, Some(10), 20),
+        "SELECT \"department\" AS \"value\", COUNT(*) AS \"count\" FROM \"users\" \
+         GROUP BY \"department\" ORDER BY \"department\" LIMIT 10 OFFSET 20"
+    );
+    assert!(PostgresBackend::distinct_values_sql("users", "status", "", ValueOrder::Frequency, Some(5), 0)
+        .ends_with("ORDER BY COUNT(*) DESC, \"status\" LIMIT 5"));
+}
+
+#[test]
+fn test_postgres_multi_column_order_by() {
+    use sqlx_repository::backends::DatabaseBackend;
+    use sqlx_repository::SortKey;
+
+    let order_by: Vec<String> = [SortKey::asc("department"), SortKey::desc("created_at").nulls_last(), SortKey::asc("id")]
+        .iter()
+        .map(PostgresBackend::order_by_sql)
+        .collect();
+    assert_eq!(
+        PostgresBackend::select_page_sql("users", &[], "", &order_by, 11, None),
+        "SELECT * FROM \"users\" ORDER BY \"department\" ASC, \"created_at\" DESC NULLS LAST, \"id\" ASC LIMIT 11"
+    );
+}
+
+#[cfg(feature = "mysql")]
+mod mysql {
+    use sqlx_repository::backends::mysql::MySqlBackend;
+    use sqlx_repository::backends::DatabaseBackend;
+
+    #[test]
+    fn test_mysql_placeholder() {
+        // MySQL placeholders are positional and ignore the index
+        assert_eq!(MySqlBackend::placeholder(1), "?");
+        assert_eq!(MySqlBackend::placeholder(12), "?");
+    }
+
+    #[test]
+    fn test_mysql_convert_type() {
+        assert_eq!(MySqlBackend::convert_type("i32"), "INT");
+        assert_eq!(MySqlBackend::convert_type("String"), "VARCHAR(255)");
+        assert_eq!(MySqlBackend::convert_type("DateTime<Utc>"), "TIMESTAMP(6)");
+        assert_eq!(MySqlBackend::convert_type("Uuid"), "BINARY(16)");
+        assert_eq!(MySqlBackend::convert_type("CustomType"), "VARCHAR(255)");
+    }
+
+    #[test]
+    fn test_mysql_select_sql() {
+        assert_eq!(
+            MySqlBackend::select_by_id_sql("users", true),
+            "SELECT * FROM `users` WHERE `id` = ? AND `deleted_at` IS NULL"
+        );
+        assert_eq!(
+            MySqlBackend::select_page_sql("users", &[], " WHERE `status` = ?", &["`id` ASC".to_string()], 10, Some(20)),
+            "SELECT * FROM `users` WHERE `status` = ? ORDER BY `id` ASC LIMIT 10 OFFSET 20"
+        );
+    }
+
+    #[test]
+    fn test_mysql_insert_and_update_sql() {
+        assert_eq!(
+            MySqlBackend::insert_sql("users", &["name", "email"], false),
+            "INSERT INTO `users` (`name`, `email`) VALUES (?, ?)"
+        );
+        assert_eq!(
+            MySqlBackend::update_sql("users", &["name", "email"], true),
+            "UPDATE `users` SET `name` = ?, `email` = ?, `updated_at` = CURRENT_TIMESTAMP WHERE `id` = ? AND `deleted_at` IS NULL"
+        );
+    }
+
+    #[test]
+    fn test_mysql_delete_sql() {
+        assert_eq!(MySqlBackend::delete_sql("users", false), "DELETE FROM `users` WHERE `id` = ?");
+        assert_eq!(
+            MySqlBackend::delete_sql("users", true),
+            "UPDATE `users` SET `deleted_at` = CURRENT_TIMESTAMP, `updated_at` = CURRENT_TIMESTAMP WHERE `id` = ? AND `deleted_at` IS NULL"
+        );
+    }
+
+    #[test]
+    fn test_mysql_dialect() {
+        assert_eq!(<MySqlBackend as DatabaseBackend>::now(), "CURRENT_TIMESTAMP");
+        assert_eq!(<MySqlBackend as DatabaseBackend>::convert_type("i64"), "BIGINT");
+        assert_eq!(
+            MySqlBackend::case_insensitive_like("name", "?"),
+            "LOWER(name) LIKE LOWER(?)"
+        );
+    }
+
+    #[test]
+    fn test_mysql_nulls_order() {
+        use sqlx_repository::SortKey;
+
+        // Emulated with an `IS NULL` sort key, as MySQL lacks NULLS FIRST / LAST
+        assert_eq!(MySqlBackend::order_by_sql(&SortKey::desc("name")), "`name` DESC");
+        assert_eq!(
+            MySqlBackend::order_by_sql(&SortKey::asc("deleted_at").nulls_first()),
+            "`deleted_at` IS NULL DESC, `deleted_at` ASC"
+        );
+        assert_eq!(
+            MySqlBackend::order_by_sql(&SortKey::desc("deleted_at").nulls_last()),
+            "`deleted_at` IS NULL ASC, `deleted_at` DESC"
+        );
+    }
+
+    #[test]
+    fn test_mysql_match_modes() {
+        use sqlx_repository::MatchMode;
+
+        assert_eq!(
+            MySqlBackend::match_sql("`name`", "?", MatchMode::Prefix),
+            "LOWER(`name`) LIKE LOWER(?) ESCAPE '!'"
+        );
+        assert_eq!(
+            MySqlBackend::match_sql("`name`", "?", MatchMode::CaseSensitive),
+            "CAST(`name` AS BINARY) LIKE ? ESCAPE '!'"
+        );
+    }
+
+    #[test]
+    fn test_mysql_sql_builders() {
+        assert_eq!(MySqlBackend::quote_identifier("app.users"), "`app`.`users`");
+        assert_eq!(MySqlBackend::quote_identifier("we`ird"), "`we``ird`");
+
+        // No RETURNING: the repository reads rows back after writing them
+        assert_eq!(
+            MySqlBackend::insert_sql("users", &["name"], true),
+            "INSERT INTO `users` (`name`, `created_at`, `updated_at`) VALUES (?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)"
+        );
+        assert_eq!(
+            MySqlBackend::update_sql("posts", &["title"], false),
+            "UPDATE `posts` SET `title` = ? WHERE `id` = ?"
+        );
+        assert_eq!(
+            MySqlBackend::restore_sql("users"),
+            "UPDATE `users` SET `deleted_at` = NULL, `updated_at` = CURRENT_TIMESTAMP WHERE `id` = ?"
+        );
+        assert_eq!(
+            MySqlBackend::aggregate_term_sql(sqlx_repository::AggregateFunc::Sum, Some("amount"), sqlx_repository::FieldType::I64),
+            "CAST(SUM(`amount`) AS SIGNED)"
+        );
+        assert_eq!(
+            MySqlBackend::estimated_count_sql("users", "").unwrap(),
+            "EXPLAIN FORMAT=TREE SELECT * FROM `users`"
+        );
+    }
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use sqlx_repository::backends::sqlite::SqliteBackend;
+    use sqlx_repository::backends::DatabaseBackend;
+
+    #[test]
+    fn test_sqlite_placeholder() {
+        assert_eq!(SqliteBackend::placeholder(1), "?1");
+        assert_eq!(SqliteBackend::placeholder(12), "?12");
+    }
+
+    #[test]
+    fn test_sqlite_convert_type() {
+        assert_eq!(SqliteBackend::convert_type("i64"), "INTEGER");
+        assert_eq!(SqliteBackend::convert_type("bool"), "INTEGER");
+        assert_eq!(SqliteBackend::convert_type("String"), "TEXT");
+        assert_eq!(SqliteBackend::convert_type("DateTime<Utc>"), "TEXT");
+        assert_eq!(SqliteBackend::convert_type("f64"), "REAL");
+        assert_eq!(SqliteBackend::convert_type("Uuid"), "BLOB");
+        assert_eq!(SqliteBackend::convert_type("CustomType"), "TEXT");
+    }
+
+    #[test]
+    fn test_sqlite_select_sql() {
+        assert_eq!(
+            SqliteBackend::select_all_sql("users", true),
+            "SELECT * FROM \"users\" WHERE \"deleted_at\" IS NULL ORDER BY \"id\""
+        );
+        assert_eq!(
+            SqliteBackend::select_page_sql("users", &[], " WHERE \"status\" = ?1", &["\"id\" ASC".to_string()], 10, Some(20)),
+            "SELECT * FROM \"users\" WHERE \"status\" = ?1 ORDER BY \"id\" ASC LIMIT 10 OFFSET 20"
+        );
+    }
+
+    #[test]
+    fn test_sqlite_insert_and_update_sql() {
+        assert_eq!(
+            SqliteBackend::insert_sql("users", &["name", "email"], false),
+            "INSERT INTO \"users\" (\"name\", \"email\") VALUES (?1, ?2) RETURNING *"
+        );
+        assert!(SqliteBackend::insert_sql("users", &["name"], true).contains("(?1, replace(strftime("));
+    }
+
+    #[test]
+    fn test_sqlite_delete_sql() {
+        assert_eq!(SqliteBackend::delete_sql("users", false), "DELETE FROM \"users\" WHERE \"id\" = ?1");
+
+        let query = SqliteBackend::delete_sql("users", true);
+        assert!(query.starts_with("UPDATE \"users\" SET \"deleted_at\" = replace(strftime("));
+        assert!(query.ends_with("WHERE \"id\" = ?1 AND \"deleted_at\" IS NULL"));
+    }
+
+    #[test]
+    fn test_sqlite_dialect() {
+        assert_eq!(SqliteBackend::case_insensitive_like("name", "?1"), "name LIKE ?1");
+        assert!(!<SqliteBackend as DatabaseBackend>::now().contains("NOW()"));
+        assert_eq!(<SqliteBackend as DatabaseBackend>::convert_type("i64"), "INTEGER");
+    }
+
+    #[test]
+    fn test_sqlite_match_modes() {
+        use sqlx_repository::MatchMode;
+
+        assert_eq!(SqliteBackend::match_pattern("a_b", MatchMode::Suffix), "%a!_b");
+        assert_eq!(SqliteBackend::match_sql("\"name\"", "?1", MatchMode::Suffix), "\"name\" LIKE ?1 ESCAPE '!'");
+
+        // SQLite's LIKE ignores case, so case-sensitive matching uses GLOB
+        assert_eq!(SqliteBackend::match_pattern("a*b?[c]", MatchMode::CaseSensitive), "*a[*]b[?][[]c]*");
+        assert_eq!(SqliteBackend::match_sql("\"name\"", "?1", MatchMode::CaseSensitive), "\"name\" GLOB ?1");
+    }
+
+    #[test]
+    fn test_sqlite_full_text_unsupported() {
+        use sqlx_repository::RepositoryError;
+
+        let err = SqliteBackend::full_text_match_sql("english", &["title"], "?1").unwrap_err();
+        assert!(matches!(err, RepositoryError::UnsupportedFeature { .. }));
+        let err = SqliteBackend::similarity_match_sql(&["title"], "?1", None).unwrap_err();
+        assert!(matches!(err, RepositoryError::UnsupportedFeature { .. }));
+        assert!(SqliteBackend::fuzzy_search_check_sql().is_none());
+        let err = SqliteBackend::estimated_count_sql("users", "").unwrap_err();
+        assert!(matches!(err, RepositoryError::UnsupportedFeature { .. }));
+    }
+
+    #[test]
+    fn test_sqlite_sql_builders() {
+        assert_eq!(SqliteBackend::quote_identifier("users"), "\"users\"");
+        assert_eq!(
+            SqliteBackend::select_by_id_sql("users", false),
+            "SELECT * FROM \"users\" WHERE \"id\" = ?1"
+        );
+        assert_eq!(
+            SqliteBackend::update_sql("posts", &["title", "content"], false),
+            "UPDATE \"posts\" SET \"title\" = ?1, \"content\" = ?2 WHERE \"id\" = ?3 RETURNING *"
+        );
+    }
+}
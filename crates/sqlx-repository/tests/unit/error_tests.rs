@@ -76,6 +76,7 @@ fn test_repository_error_unsupported_feature() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_literal_unwrap)]
 fn test_repository_result_type_alias() {
     // Test that RepositoryResult is properly aliased
     let success: RepositoryResult<String> = Ok("test".to_string());
@@ -84,4 +85,71 @@ fn test_repository_result_type_alias() {
     
     let failure: RepositoryResult<String> = Err(RepositoryError::validation("test error"));
     assert!(failure.is_err());
-}
\ No newline at end of file
+}
+/// Minimal database error carrying a SQLSTATE, standing in for a driver error
+#[derive(Debug)]
+struct StateError(&'static str);
+
+impl std::fmt::Display for StateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "database error {}", self.0)
+    }
+}
+
+impl std::error::Error for StateError {}
+
+impl sqlx::error::DatabaseError for StateError {
+    fn message(&self) -> &str {
+        "database error"
+    }
+
+    fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+        Some(self.0.into())
+    }
+
+    fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+        self
+    }
+
+    fn kind(&self) -> sqlx::error::ErrorKind {
+        sqlx::error::ErrorKind::Other
+    }
+}
+
+fn database_error(sqlstate: &'static str) -> RepositoryError {
+    RepositoryError::Database(sqlx::Error::Database(Box::new(StateError(sqlstate))))
+}
+
+#[test]
+fn test_repository_error_retryable_sqlstates() {
+    assert_eq!(database_error("40001").sqlstate().as_deref(), Some("40001"));
+    assert!(database_error(sqlx_repository::error::SERIALIZATION_FAILURE).is_retryable());
+    assert!(database_error(sqlx_repository::error::DEADLOCK_DETECTED).is_retryable());
+
+    // Constraint violations and non-database errors are not retried
+    assert!(!database_error("23505").is_retryable());
+    assert!(!RepositoryError::Database(sqlx::Error::RowNotFound).is_retryable());
+    assert!(!RepositoryError::validation("bad input").is_retryable());
+    assert_eq!(RepositoryError::validation("bad input").sqlstate(), None);
+}
+
+#[test]
+fn test_repository_error_retries_exhausted() {
+    let error = RepositoryError::RetriesExhausted {
+        attempts: 3,
+        source: Box::new(database_error("40P01")),
+    };
+
+    assert_eq!(error.to_string(), "Transaction failed after 3 attempts: Database error: error returned from database: database error 40P01");
+    // The wrapper itself is final and not retried again
+    assert!(!error.is_retryable());
+    assert!(std::error::Error::source(&error).is_some());
+}
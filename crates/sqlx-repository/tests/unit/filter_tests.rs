@@ -0,0 +1,242 @@
+//! Unit tests for typed filter conditions
+
+use sqlx_repository::{Filter, FilterOp, FilterValue, SearchParams};
+
+#[test]
+fn test_filter_constructors() {
+    assert_eq!(Filter::gt("age", 30).op, FilterOp::Gt(FilterValue::Int(30)));
+    assert_eq!(Filter::ne("status", "active").op, FilterOp::Ne(FilterValue::Text("active".to_string())));
+    assert_eq!(Filter::eq("published", true).op, FilterOp::Eq(FilterValue::Bool(true)));
+    assert_eq!(
+        Filter::is_in("status", ["a", "b"]).op,
+        FilterOp::In(vec!["a".into(), "b".into()])
+    );
+    assert_eq!(
+        Filter::between("score", 1.5, 2.5).op,
+        FilterOp::Between(FilterValue::Float(1.5), FilterValue::Float(2.5))
+    );
+    assert_eq!(Filter::is_null("deleted_by").field, "deleted_by");
+}
+
+#[test]
+fn test_filter_deserialization() {
+    let filters: Vec<Filter> = serde_json::from_str(
+        r#"[
+            {"field": "created_at", "op": "gt", "value": "2024-01-01T00:00:00Z"},
+            {"field": "status", "op": "in", "value": ["active", "pending"]},
+            {"field": "status", "op": "not_in", "value": []},
+            {"field": "age", "op": "between", "value": [18, 65]},
+            {"field": "published", "op": "eq", "value": true},
+            {"field": "deleted_by", "op": "is_null"},
+            {"field": "name", "op": "starts_with", "value": "Al"},
+            {"field": "name", "op": "contains", "value": "li"}
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(filters[0], Filter::gt("created_at", "2024-01-01T00:00:00Z"));
+    assert_eq!(filters[1], Filter::is_in("status", ["active", "pending"]));
+    assert_eq!(filters[2], Filter::not_in("status", Vec::<String>::new()));
+    assert_eq!(filters[3], Filter::between("age", 18, 65));
+    assert_eq!(filters[4], Filter::eq("published", true));
+    assert_eq!(filters[5], Filter::is_null("deleted_by"));
+    assert_eq!(filters[6], Filter::starts_with("name", "Al"));
+    assert_eq!(filters[7], Filter::contains("name", "li"));
+}
+
+#[test]
+fn test_filter_deserialization_rejects_bad_input() {
+    // Unknown operator
+    assert!(serde_json::from_str::<Filter>(r#"{"field": "age", "op": "like", "value": 1}"#).is_err());
+    // Missing operand
+    assert!(serde_json::from_str::<Filter>(r#"{"field": "age", "op": "gt"}"#).is_err());
+    // `between` needs exactly two bounds
+    assert!(serde_json::from_str::<Filter>(r#"{"field": "age", "op": "between", "value": [1]}"#).is_err());
+}
+
+#[test]
+fn test_filter_serialization_round_trip() {
+    let filter = Filter::is_in("status", ["active", "pending"]);
+    let json = serde_json::to_value(&filter).unwrap();
+
+    assert_eq!(json, serde_json::json!({"field": "status", "op": "in", "value": ["active", "pending"]}));
+    assert_eq!(serde_json::from_value::<Filter>(json).unwrap(), filter);
+    assert_eq!(
+        serde_json::to_value(Filter::is_not_null("deleted_at")).unwrap(),
+        serde_json::json!({"field": "deleted_at", "op": "is_not_null"})
+    );
+}
+
+#[test]
+fn test_search_params_conditions_default_to_empty() {
+    let params: SearchParams = serde_json::from_str(
+        r#"{"query": null, "filters": {}, "page": 0, "per_page": 10, "sort_by": null, "sort_order": "asc", "scope": "active"}"#,
+    )
+    .unwrap();
+    assert!(params.conditions.is_empty());
+
+    let params: SearchParams = serde_json::from_str(
+        r#"{"query": null, "filters": {"status": "active"}, "page": 0, "per_page": 10, "sort_by": null,
+            "sort_order": "asc", "scope": "active",
+            "conditions": [{"field": "age", "op": "gte", "value": 21}]}"#,
+    )
+    .unwrap();
+    assert_eq!(params.filters.get("status"), Some(&"active".to_string()));
+    assert_eq!(params.conditions, vec![Filter::gte("age", 21)]);
+}
+
+#[test]
+fn test_filter_value_display() {
+    assert_eq!(FilterValue::from("text").to_string(), "text");
+    assert_eq!(FilterValue::from(42).to_string(), "42");
+    assert_eq!(FilterValue::from(false).to_string(), "false");
+}
+
+#[test]
+fn test_field_type_parses_text_input() {
+    use sqlx_repository::{FieldType, SqlValue};
+
+    let text = |value: &str| FilterValue::Text(value.to_string());
+
+    assert_eq!(FieldType::Bool.parse("published", &text("true")).unwrap(), SqlValue::Bool(true));
+    assert_eq!(FieldType::Bool.parse("published", &text("0")).unwrap(), SqlValue::Bool(false));
+    assert_eq!(FieldType::I32.parse("user_id", &text("42")).unwrap(), SqlValue::I32(42));
+    assert_eq!(FieldType::I64.parse("user_id", &FilterValue::Int(7)).unwrap(), SqlValue::I64(7));
+    assert_eq!(FieldType::F64.parse("score", &FilterValue::Int(2)).unwrap(), SqlValue::F64(2.0));
+    assert_eq!(FieldType::Text.parse("status", &FilterValue::Int(5)).unwrap(), SqlValue::Text("5".to_string()));
+
+    let timestamp = "2024-03-01T12:30:00Z".parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+    assert_eq!(
+        FieldType::DateTime.parse("created_at", &text("2024-03-01T12:30:00Z")).unwrap(),
+        SqlValue::DateTime(timestamp)
+    );
+    assert_eq!(
+        FieldType::DateTime.parse("created_at", &text("2024-03-01")).unwrap(),
+        SqlValue::DateTime("2024-03-01T00:00:00Z".parse().unwrap())
+    );
+    assert_eq!(
+        FieldType::NaiveDate.parse("birthday", &text("1990-05-17")).unwrap(),
+        SqlValue::NaiveDate(chrono::NaiveDate::from_ymd_opt(1990, 5, 17).unwrap())
+    );
+    assert!(matches!(
+        FieldType::Uuid.parse("id", &text("67e55044-10b1-426f-9247-bb680e5fe0c8")).unwrap(),
+        SqlValue::Uuid(_)
+    ));
+}
+
+#[test]
+fn test_field_type_parses_relative_times() {
+    use chrono::{Duration, Utc};
+    use sqlx_repository::{FieldType, SqlValue};
+
+    let parse = |field_type: FieldType, value: &str| field_type.parse("created_at", &FilterValue::Text(value.to_string()));
+    let close_to = |value: SqlValue, expected: chrono::DateTime<Utc>| match value {
+        SqlValue::DateTime(time) => (time - expected).abs() < Duration::seconds(5),
+        other => panic!("expected a timestamp, got {:?}", other),
+    };
+
+    assert!(close_to(parse(FieldType::DateTime, "now").unwrap(), Utc::now()));
+    assert!(close_to(parse(FieldType::DateTime, "now-7d").unwrap(), Utc::now() - Duration::days(7)));
+    assert!(close_to(parse(FieldType::DateTime, "now+2h").unwrap(), Utc::now() + Duration::hours(2)));
+    assert!(close_to(parse(FieldType::DateTime, "now-1w").unwrap(), Utc::now() - Duration::weeks(1)));
+    assert!(matches!(parse(FieldType::NaiveDateTime, "now-30m").unwrap(), SqlValue::NaiveDateTime(_)));
+    assert_eq!(
+        parse(FieldType::NaiveDate, "now-1d").unwrap(),
+        SqlValue::NaiveDate((Utc::now() - Duration::days(1)).date_naive())
+    );
+
+    for value in ["now-7", "now-7y", "now7d", "now-d", "nowish"] {
+        assert!(parse(FieldType::DateTime, value).is_err(), "{}", value);
+    }
+    // Non-ASCII offsets are rejected, not split mid-character
+    for value in ["nowé", "now-7é", "now+é"] {
+        let err = parse(FieldType::DateTime, value).expect_err(value);
+        assert!(matches!(err, sqlx_repository::RepositoryError::Validation(_)), "{}", value);
+    }
+    assert!(parse(FieldType::Text, "now-7d").is_ok());
+    assert!(parse(FieldType::I32, "now-7d").is_err());
+}
+
+#[test]
+fn test_field_type_kinds() {
+    use sqlx_repository::FieldType;
+
+    assert!(FieldType::I32.is_numeric() && FieldType::F64.is_numeric());
+    assert!(!FieldType::Text.is_numeric() && !FieldType::DateTime.is_numeric());
+    assert!(FieldType::DateTime.is_temporal() && FieldType::NaiveDate.is_temporal());
+    assert!(!FieldType::Bool.is_temporal() && !FieldType::I64.is_temporal());
+}
+
+#[test]
+fn test_field_type_rejects_unparseable_input() {
+    use sqlx_repository::{FieldType, RepositoryError};
+
+    let err = FieldType::Bool
+        .parse("published", &FilterValue::Text("maybe".to_string()))
+        .unwrap_err();
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    assert_eq!(
+        err.to_string(),
+        "Validation error: Invalid value 'maybe' for field 'published': expected a boolean"
+    );
+
+    assert!(FieldType::I16.parse("rank", &FilterValue::Int(100_000)).is_err());
+    assert!(FieldType::I32.parse("user_id", &FilterValue::Float(1.5)).is_err());
+    assert!(FieldType::I32.parse("user_id", &FilterValue::Bool(true)).is_err());
+    assert!(FieldType::Uuid.parse("id", &FilterValue::Text("not-a-uuid".to_string())).is_err());
+    assert!(FieldType::DateTime.parse("created_at", &FilterValue::Text("yesterday".to_string())).is_err());
+}
+
+#[test]
+fn test_filter_expr_deserialization() {
+    use sqlx_repository::FilterExpr;
+
+    let expr: FilterExpr = serde_json::from_str(
+        r#"{"and": [
+            {"or": [
+                {"field": "status", "op": "eq", "value": "active"},
+                {"field": "status", "op": "eq", "value": "pending"}
+            ]},
+            {"not": {"field": "department", "op": "eq", "value": "sales"}}
+        ]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        expr,
+        FilterExpr::and([
+            FilterExpr::or([Filter::eq("status", "active"), Filter::eq("status", "pending")]),
+            FilterExpr::not(Filter::eq("department", "sales")),
+        ])
+    );
+
+    // A bare condition is an expression too
+    let expr: FilterExpr = serde_json::from_str(r#"{"field": "age", "op": "gt", "value": 30}"#).unwrap();
+    assert_eq!(expr, FilterExpr::Condition(Filter::gt("age", 30)));
+
+    // Round-trips through JSON
+    let json = serde_json::to_value(FilterExpr::not(FilterExpr::or([Filter::is_null("deleted_by")]))).unwrap();
+    assert_eq!(json, serde_json::json!({"not": {"or": [{"field": "deleted_by", "op": "is_null"}]}}));
+
+    assert!(serde_json::from_str::<FilterExpr>(r#"{"xor": []}"#).is_err());
+}
+
+#[test]
+fn test_filter_expr_validation() {
+    use sqlx_repository::{FilterExpr, RepositoryError};
+
+    let filterable = ["status", "department"];
+    let expr = FilterExpr::and([
+        FilterExpr::or([Filter::eq("status", "active"), Filter::eq("status", "pending")]),
+        FilterExpr::not(Filter::eq("department", "sales")),
+    ]);
+    assert!(expr.validate(&filterable).is_ok());
+
+    let nested = FilterExpr::or([
+        FilterExpr::from(Filter::eq("status", "active")),
+        FilterExpr::not(FilterExpr::and([Filter::eq("password_hash", "x")])),
+    ]);
+    let err = nested.validate(&filterable).unwrap_err();
+    assert!(matches!(err, RepositoryError::Validation(_)));
+    assert_eq!(err.to_string(), "Validation error: Field 'password_hash' is not filterable");
+}
@@ -5,4 +5,10 @@
 
 pub mod error_tests;
 pub mod search_params_tests;
-pub mod backend_tests;
\ No newline at end of file
+pub mod backend_tests;
+pub mod retry_tests;
+pub mod filter_tests;
+pub mod aggregate_tests;
+pub mod query_string_tests;
+pub mod query_language_tests;
+
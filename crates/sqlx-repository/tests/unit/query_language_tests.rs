@@ -0,0 +1,89 @@
+//! Unit tests for the search-box query language
+
+use sqlx_repository::{Filter, RepositoryError, SearchParams};
+
+#[test]
+fn test_parse_search() {
+    let params = SearchParams::from_search(
+        r#"status:active department:eng created_at>2026-01-01 -status:banned "john doe""#,
+    )
+    .unwrap();
+
+    assert_eq!(params.query.as_deref(), Some("john doe"));
+    assert_eq!(
+        params.conditions,
+        vec![
+            Filter::eq("status", "active"),
+            Filter::eq("department", "eng"),
+            Filter::gt("created_at", "2026-01-01"),
+            Filter::ne("status", "banned"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_search_operators() {
+    let params = SearchParams::from_search("age>=18 age:<65 score<=9.5 -rank>3 -rank<=1 -age:>=90").unwrap();
+    assert_eq!(
+        params.conditions,
+        vec![
+            Filter::gte("age", "18"),
+            Filter::lt("age", "65"),
+            Filter::lte("score", "9.5"),
+            Filter::lte("rank", "3"),
+            Filter::gt("rank", "1"),
+            Filter::lt("age", "90"),
+        ]
+    );
+    assert_eq!(params.query, None);
+}
+
+#[test]
+fn test_parse_search_lists_and_quotes() {
+    let params = SearchParams::from_search(
+        r#"status:active,pending -role:guest,bot name:"Doe, Jane" note:"say \"hi\"" plain words"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        params.conditions,
+        vec![
+            Filter::is_in("status", ["active", "pending"]),
+            Filter::not_in("role", ["guest", "bot"]),
+            Filter::eq("name", "Doe, Jane"),
+            Filter::eq("note", r#"say "hi""#),
+        ]
+    );
+    assert_eq!(params.query.as_deref(), Some("plain words"));
+}
+
+#[test]
+fn test_parse_search_free_text() {
+    let params = SearchParams::from_search("  e-mail - 100%  ").unwrap();
+    assert_eq!(params.query.as_deref(), Some("e-mail - 100%"));
+    assert!(params.conditions.is_empty());
+
+    let params = SearchParams::from_search("").unwrap();
+    assert_eq!(params.query, None);
+}
+
+#[test]
+fn test_parse_search_errors_report_column() {
+    let cases = [
+        (r#"status:active "john"#, 15, "unterminated quote"),
+        ("name:", 6, "expected a value for 'name'"),
+        ("age> x", 5, "expected a value for 'age'"),
+        ("a -spam", 3, "free text cannot be negated"),
+        (r#"-"john doe""#, 1, "free text cannot be negated"),
+        (r#"name:"x"y"#, 9, "expected whitespace after closing quote"),
+    ];
+
+    for (input, column, reason) in cases {
+        match SearchParams::from_search(input) {
+            Err(RepositoryError::Validation(message)) => {
+                assert_eq!(message, format!("Invalid search at column {}: {}", column, reason), "{}", input);
+            }
+            other => panic!("{}: expected a validation error, got {:?}", input, other.map(|_| ())),
+        }
+    }
+}
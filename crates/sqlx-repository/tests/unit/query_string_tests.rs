@@ -0,0 +1,131 @@
+//! Unit tests for the query-string form of search params
+
+use sqlx_repository::{CountMode, Filter, FilterExpr, MatchMode, RecordScope, RepositoryError, SearchParams, SortKey, SortOrder};
+
+#[test]
+fn test_parse_query_string() {
+    let params = SearchParams::from_query_string(
+        "?q=jane%20doe&match=prefix&filter[status]=active&filter%5Bage%5D%5Bgte%5D=30\
+         &filter[role][in]=admin&filter[role][in]=editor&filter[deleted_by][is_null]\
+         &filter[score][between]=1&filter[score][between]=5&sort=-name,created_at:nulls_last\
+         &page[number]=2&page[size]=20&count=none&scope=all&facets=status,%20role",
+    )
+    .unwrap();
+
+    assert_eq!(params.query.as_deref(), Some("jane doe"));
+    assert_eq!(params.match_mode, MatchMode::Prefix);
+    assert_eq!(params.filters.get("status").map(String::as_str), Some("active"));
+    assert_eq!(
+        params.conditions,
+        vec![
+            Filter::gte("age", "30"),
+            Filter::is_in("role", ["admin", "editor"]),
+            Filter::is_null("deleted_by"),
+            Filter::between("score", "1", "5"),
+        ]
+    );
+    assert_eq!(params.sort, vec![SortKey::desc("name"), SortKey::asc("created_at").nulls_last()]);
+    assert_eq!(params.page, 2);
+    assert_eq!(params.per_page, 20);
+    assert_eq!(params.count_mode, CountMode::None);
+    assert!(matches!(params.scope, RecordScope::All));
+    assert_eq!(params.facets, vec!["status", "role"]);
+}
+
+#[test]
+fn test_parse_empty_query_string() {
+    let params = SearchParams::from_query_string("").unwrap();
+    let defaults = SearchParams::default();
+
+    assert_eq!(params.query, defaults.query);
+    assert_eq!(params.page, defaults.page);
+    assert_eq!(params.per_page, defaults.per_page);
+    assert!(params.conditions.is_empty());
+}
+
+#[test]
+fn test_query_string_round_trip() {
+    let params = SearchParams {
+        query: Some("café & co".to_string()),
+        match_mode: MatchMode::Fuzzy,
+        similarity_threshold: Some(0.4),
+        order_by_rank: true,
+        filters: [("status".to_string(), "active".to_string())].into(),
+        conditions: vec![
+            Filter::not_in("role", ["guest"]),
+            Filter::is_in("team", Vec::<&str>::new()),
+            Filter::contains("email", "@example.com"),
+        ],
+        filter: Some(FilterExpr::not(Filter::eq("department", "sales"))),
+        cursor: Some("abc.def".to_string()),
+        per_page: 50,
+        sort: vec![SortKey::desc("created_at")],
+        ..Default::default()
+    };
+
+    let query = params.to_query_string();
+    let parsed = SearchParams::from_query_string(&query).unwrap();
+
+    assert_eq!(parsed.query, params.query);
+    assert_eq!(parsed.match_mode, params.match_mode);
+    assert_eq!(parsed.similarity_threshold, params.similarity_threshold);
+    assert!(parsed.order_by_rank);
+    assert_eq!(parsed.filters, params.filters);
+    assert_eq!(parsed.conditions, params.conditions);
+    assert_eq!(parsed.filter, params.filter);
+    assert_eq!(parsed.cursor, params.cursor);
+    assert_eq!(parsed.per_page, 50);
+    assert_eq!(parsed.sort, params.sort);
+    assert_eq!(parsed.to_query_string(), query);
+}
+
+#[test]
+fn test_to_query_string_writes_only_changes() {
+    assert_eq!(SearchParams::default().to_query_string(), "");
+
+    let params = SearchParams {
+        page: 3,
+        sort_by: Some("name".to_string()),
+        sort_order: SortOrder::Desc,
+        ..Default::default()
+    };
+    assert_eq!(params.to_query_string(), "sort=-name&page%5Bnumber%5D=3");
+}
+
+#[test]
+fn test_query_string_errors_name_parameter() {
+    let cases = [
+        ("page[size]=ten", "page[size]", "expected a non-negative integer, got 'ten'"),
+        ("match=loose", "match", "unknown value 'loose'"),
+        ("q=a&q=b", "q", "given more than once"),
+        ("filter[status]=a&filter[status]=b", "filter[status]", "given more than once"),
+        ("filter[age][gt]=1&filter[age][gt]=2", "filter[age][gt]", "given more than once"),
+        ("filter[age][around]=3", "filter[age][around]", "unknown filter operator 'around'"),
+        ("filter[age][between]=3", "filter[age][between]", "expected 2 values, got 1"),
+        ("filter[age]x=3", "filter[age]x", "expected filter[field] or filter[field][op]"),
+        ("filter[]=3", "filter[]", "missing field name"),
+        ("filter[name][is_null]=no", "filter[name][is_null]", "expected 'true' or no value"),
+        ("rank=yes", "rank", "expected 'true' or 'false', got 'yes'"),
+        ("sort=-", "sort", "Invalid sort key '-'"),
+        ("utm_source=mail", "utm_source", "unknown parameter"),
+    ];
+
+    for (query, key, reason) in cases {
+        match SearchParams::from_query_string(query) {
+            Err(RepositoryError::Validation(message)) => {
+                assert!(message.contains(&format!("'{}'", key)), "{}: {}", query, message);
+                assert!(message.contains(reason), "{}: {}", query, message);
+            }
+            other => panic!("{}: expected a validation error, got {:?}", query, other.map(|_| ())),
+        }
+    }
+}
+
+#[test]
+fn test_from_query_pairs() {
+    let pairs = vec![("filter[status]", "active"), ("page[number]", "1")];
+    let params = SearchParams::from_query_pairs(pairs).unwrap();
+
+    assert_eq!(params.filters.get("status").map(String::as_str), Some("active"));
+    assert_eq!(params.page, 1);
+}
@@ -0,0 +1,68 @@
+//! Unit tests for the transaction retry policy
+
+use sqlx_repository::RetryPolicy;
+use std::time::Duration;
+
+#[test]
+fn test_retry_policy_default() {
+    let policy = RetryPolicy::default();
+    assert_eq!(policy.max_attempts, 5);
+    assert_eq!(policy.initial_backoff, Duration::from_millis(10));
+    assert_eq!(policy.max_backoff, Duration::from_secs(1));
+    assert!(policy.jitter);
+
+    assert_eq!(RetryPolicy::no_retry().max_attempts, 1);
+}
+
+#[test]
+fn test_retry_policy_exponential_backoff() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(500),
+        multiplier: 2.0,
+        ..Default::default()
+    };
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(400));
+    // Capped at max_backoff
+    assert_eq!(policy.backoff(4), Duration::from_millis(500));
+    assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(500));
+}
+
+#[test]
+fn test_retry_policy_constant_backoff() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(50),
+        multiplier: 1.0,
+        ..Default::default()
+    };
+
+    assert_eq!(policy.backoff(1), policy.backoff(5));
+}
+
+#[test]
+fn test_retry_policy_odd_multipliers() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(500),
+        multiplier: -2.0,
+        ..Default::default()
+    };
+    assert_eq!(policy.backoff(2), Duration::ZERO);
+    assert_eq!(policy.backoff(3), Duration::from_millis(400));
+
+    for multiplier in [f64::NAN, f64::NEG_INFINITY] {
+        let policy = RetryPolicy { multiplier, ..policy.clone() };
+        assert_eq!(policy.backoff(2), Duration::ZERO);
+    }
+
+    // Delays beyond what a Duration holds are capped too
+    let policy = RetryPolicy {
+        multiplier: f64::INFINITY,
+        max_backoff: Duration::MAX,
+        ..Default::default()
+    };
+    assert_eq!(policy.backoff(2), Duration::MAX);
+}
@@ -1,6 +1,6 @@
 //! Unit tests for search functionality
 
-use sqlx_repository::{SearchParams, SearchResult, SortOrder, RecordScope};
+use sqlx_repository::{CountMode, MatchMode, NullsOrder, SearchParams, SearchResult, SortKey, SortOrder, RecordScope};
 use std::collections::HashMap;
 
 #[test]
@@ -10,10 +10,12 @@ fn test_search_params_default() {
     assert_eq!(params.query, None);
     assert_eq!(params.filters.len(), 0);
     assert_eq!(params.page, 0);
-    assert_eq!(params.per_page, 10);
+    assert_eq!(params.per_page, 0);
     assert_eq!(params.sort_by, None);
     assert!(matches!(params.sort_order, SortOrder::Asc));
     assert!(matches!(params.scope, RecordScope::Active));
+    assert_eq!(params.cursor, None);
+    assert_eq!(params.match_mode, MatchMode::Contains);
 }
 
 #[test]
@@ -30,6 +32,7 @@ fn test_search_params_custom() {
         sort_by: Some("name".to_string()),
         sort_order: SortOrder::Desc,
         scope: RecordScope::All,
+        ..Default::default()
     };
     
     assert_eq!(params.query, Some("john".to_string()));
@@ -67,6 +70,23 @@ fn test_search_result_new() {
     assert_eq!(result.total_pages, 3); // ceil(25/10) = 3
 }
 
+#[test]
+fn test_search_result_cursors() {
+    let result = SearchResult::new(vec!["item"], 1, 0, 10);
+    assert_eq!(result.next_cursor, None);
+    assert_eq!(result.prev_cursor, None);
+    
+    let result = result.with_cursors(Some("next".to_string()), None);
+    assert_eq!(result.next_cursor.as_deref(), Some("next"));
+    assert_eq!(result.prev_cursor, None);
+}
+
+#[test]
+fn test_sort_order_reverse() {
+    assert_eq!(SortOrder::Asc.reverse(), SortOrder::Desc);
+    assert_eq!(SortOrder::Desc.reverse(), SortOrder::Asc);
+}
+
 #[test]
 fn test_search_result_total_pages_calculation() {
     // Test exact division
@@ -99,6 +119,30 @@ fn test_search_result_has_next_page() {
     // Single page
     let result = SearchResult::new(vec!["item"], 5, 0, 10); // page 0 of 1
     assert!(!result.has_next_page());
+    
+    // The last possible page number doesn't overflow
+    let result = SearchResult::new(Vec::<String>::new(), 25, u32::MAX, 10);
+    assert!(!result.has_next_page());
+}
+
+#[test]
+fn test_search_result_count_mode() {
+    let result = SearchResult::new(vec!["item"], 25, 1, 10);
+    assert_eq!(result.count_mode, CountMode::Exact);
+    
+    // Without an exact total, the fetched rows decide whether a page follows
+    let result = SearchResult::new(vec!["item"], 11, 1, 10).with_count_mode(CountMode::None, true);
+    assert_eq!(result.count_mode, CountMode::None);
+    assert!(result.has_next_page());
+    let result = SearchResult::new(vec!["item"], 1000, 1, 10).with_count_mode(CountMode::Estimated, false);
+    assert!(!result.has_next_page());
+    
+    let params: SearchParams = serde_json::from_str(r#"{
+        "count_mode": "estimated",
+        "filters": {}, "page": 0, "per_page": 10, "sort_order": "asc", "scope": "active"
+    }"#).unwrap();
+    assert_eq!(params.count_mode, CountMode::Estimated);
+    assert_eq!(SearchParams::default().count_mode, CountMode::Exact);
 }
 
 #[test]
@@ -141,6 +185,93 @@ fn test_search_params_serde_deserialization() {
     assert!(matches!(params.scope, RecordScope::All));
 }
 
+#[test]
+fn test_sort_key_parse_list() {
+    let keys = SortKey::parse_list("department, -created_at:nulls_last,+name:nulls_first").unwrap();
+    assert_eq!(
+        keys,
+        [
+            SortKey::asc("department"),
+            SortKey::desc("created_at").nulls_last(),
+            SortKey::asc("name").nulls_first(),
+        ]
+    );
+    assert_eq!(keys[1].nulls, NullsOrder::Last);
+    
+    // Display gives back the compact form
+    let compact: Vec<String> = keys.iter().map(ToString::to_string).collect();
+    assert_eq!(compact.join(","), "department,-created_at:nulls_last,name:nulls_first");
+    
+    assert!(SortKey::parse_list("").unwrap().is_empty());
+    for bad in ["name,,email", "-", "name:nulls_middle"] {
+        assert!(SortKey::parse_list(bad).is_err(), "{} should not parse", bad);
+    }
+}
+
+#[test]
+fn test_sort_key_reverse() {
+    assert_eq!(SortKey::asc("name").reverse(), SortKey::desc("name"));
+    assert_eq!(SortKey::desc("name").nulls_first().reverse(), SortKey::asc("name").nulls_last());
+}
+
+#[test]
+fn test_search_params_sort_keys() {
+    // sort_by and sort_order, then the id as a tie-breaker
+    let params = SearchParams {
+        sort_by: Some("name".to_string()),
+        sort_order: SortOrder::Desc,
+        ..Default::default()
+    };
+    assert_eq!(params.sort_keys(), [SortKey::desc("name"), SortKey::desc("id")]);
+    assert_eq!(SearchParams::default().sort_keys(), [SortKey::asc("id")]);
+    
+    // An explicit sort list takes precedence
+    let params: SearchParams = serde_json::from_str(r#"{
+        "filters": {}, "page": 0, "per_page": 10, "sort_by": "email", "sort_order": "asc", "scope": "active",
+        "sort": "department,-created_at:nulls_last"
+    }"#).unwrap();
+    assert_eq!(
+        params.sort_keys(),
+        [SortKey::asc("department"), SortKey::desc("created_at").nulls_last(), SortKey::desc("id")]
+    );
+    
+    // ...also as a list of keys, where an id key needs no tie-breaker
+    let params: SearchParams = serde_json::from_str(r#"{
+        "filters": {}, "page": 0, "per_page": 10, "sort_order": "asc", "scope": "active",
+        "sort": [{"field": "name"}, {"field": "id", "order": "desc", "nulls": "first"}]
+    }"#).unwrap();
+    assert_eq!(params.sort_keys(), [SortKey::asc("name"), SortKey::desc("id").nulls_first()]);
+    
+    let result: Result<SearchParams, _> = serde_json::from_str(r#"{
+        "filters": {}, "page": 0, "per_page": 10, "sort_order": "asc", "scope": "active", "sort": "name:sideways"
+    }"#);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_match_mode_deserialization() {
+    let params: SearchParams = serde_json::from_str(r#"{
+        "query": "50%", "match_mode": "case_sensitive",
+        "filters": {}, "page": 0, "per_page": 10, "sort_order": "asc", "scope": "active"
+    }"#).unwrap();
+    assert_eq!(params.match_mode, MatchMode::CaseSensitive);
+    
+    let mode: MatchMode = serde_json::from_str(r#""prefix""#).unwrap();
+    assert_eq!(mode, MatchMode::Prefix);
+    assert!(serde_json::from_str::<MatchMode>(r#""soundex""#).is_err());
+}
+
+#[test]
+fn test_fuzzy_search_params_deserialization() {
+    let params: SearchParams = serde_json::from_str(r#"{
+        "query": "jon", "match_mode": "fuzzy", "similarity_threshold": 0.4,
+        "filters": {}, "page": 0, "per_page": 10, "sort_order": "asc", "scope": "active"
+    }"#).unwrap();
+    assert_eq!(params.match_mode, MatchMode::Fuzzy);
+    assert_eq!(params.similarity_threshold, Some(0.4));
+    assert_eq!(SearchParams::default().similarity_threshold, None);
+}
+
 #[test]
 fn test_search_result_serde_serialization() {
     // Test that SearchResult can be serialized to JSON
@@ -157,4 +288,54 @@ fn test_search_result_serde_serialization() {
     assert_eq!(parsed["total_pages"], 3);
     assert_eq!(parsed["items"][0], "item1");
     assert_eq!(parsed["items"][1], "item2");
-}
\ No newline at end of file
+}
+#[test]
+fn test_search_params_normalize() {
+    let mut params = SearchParams {
+        per_page: 0,
+        ..Default::default()
+    };
+    params.normalize(25, 100);
+    assert_eq!(params.per_page, 25);
+
+    params.per_page = 500;
+    params.normalize(25, 100);
+    assert_eq!(params.per_page, 100);
+
+    params.per_page = 40;
+    params.normalize(25, 100);
+    assert_eq!(params.per_page, 40);
+}
+
+#[test]
+fn test_search_params_validate() {
+    let params = |page: u32, per_page: u32| SearchParams {
+        page,
+        per_page,
+        ..Default::default()
+    };
+
+    assert!(params(3, 100).validate(100).is_ok());
+    assert_eq!(
+        params(0, 101).validate(100).unwrap_err().to_string(),
+        "Validation error: per_page must be between 1 and 100, got 101"
+    );
+    assert!(params(0, 0).validate(100).is_err());
+    assert_eq!(
+        params(u32::MAX / 10, 20).validate(100).unwrap_err().to_string(),
+        format!("Validation error: Page {} is out of range for 20 items per page", u32::MAX / 10)
+    );
+    
+    // The page must end within range too, not just start there
+    assert!(params(u32::MAX - 1, 1).validate(100).is_ok());
+    assert!(params(u32::MAX, 1).validate(100).is_err());
+    assert!(params(u32::MAX / 20 - 1, 20).validate(100).is_ok());
+    assert!(params(u32::MAX / 20, 20).validate(100).is_err());
+
+    // Cursor pages don't use the page number
+    let cursor_page = SearchParams {
+        cursor: Some("cursor".to_string()),
+        ..params(u32::MAX, 20)
+    };
+    assert!(cursor_page.validate(100).is_ok());
+}
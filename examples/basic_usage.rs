@@ -76,7 +76,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
             
             // Example: Delete user
-            if let Ok(_) = user_repo.delete(user.id).await {
+            if user_repo.delete(user.id).await.is_ok() {
                 println!("🗑️  Deleted user");
             }
         }